@@ -0,0 +1,165 @@
+use std::time::{ Duration, Instant };
+
+use crate::{ input::{ self, Source }, parse::ParseError, report::{ self, Format, Record }, Part, Solution };
+
+// Parse errors quote the input they came from, anything else is just its message.
+pub fn describe_error(error: &(dyn std::error::Error + 'static), source_name: &str, lines: &[&str]) -> String {
+    match error.downcast_ref::<ParseError>() {
+        Some(e) => e.diagnostic(source_name, lines),
+        None => format!("error: {}\n", error),
+    }
+}
+
+pub const DAY_USAGE: &str = "Usage: day<N> [--format <text|json|csv>] [INPUT...]
+
+Each INPUT is a file, a glob pattern such as \"inputs/*.txt\", or - for stdin. With no INPUT, stdin is read.
+When there's more than one input, each one's answers are printed under its name.
+json and csv print one record per part and input, with the answer, the time it took, and any error.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayOptions {
+    pub format: Format,
+    pub inputs: Vec<String>,
+}
+
+// Returns None when help was asked for.
+pub fn parse_day_args(mut args: impl Iterator<Item = String>) -> Result<Option<DayOptions>, String> {
+    let mut format = Format::default();
+    let mut inputs: Vec<String> = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--format" | "-f" => {
+                let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+                format = value.parse().map_err(|e| format!("{}", e))?;
+            },
+            flag if flag.starts_with('-') && flag != "-" => return Err(format!("Unknown option {}", flag)),
+            input => inputs.push(String::from(input)),
+        }
+    }
+    Ok(Some(DayOptions { format, inputs }))
+}
+
+// Solves the given parts of one source, writing a diagnostic to stderr for anything that fails.
+pub fn solve_source(solution: &dyn Solution, source: &Source, parts: &[Part]) -> Vec<Record> {
+    let input = source.to_string();
+    let record = |part: Part, answer: Option<String>, elapsed: Duration, error: Option<String>| Record {
+        day: solution.day(),
+        part,
+        input: input.clone(),
+        answer,
+        elapsed,
+        error,
+    };
+    let lines = match source.read_lines() {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("error: {}: {}", source, e);
+            return parts.iter().map(|p| record(*p, None, Duration::ZERO, Some(e.to_string()))).collect()
+        },
+    };
+    let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+    let mut records: Vec<Record> = Vec::new();
+    for part in parts {
+        let start = Instant::now();
+        let result = solution.solve(*part, &lines_str[..]);
+        let elapsed = start.elapsed();
+        records.push(match result {
+            Ok(answer) => record(*part, Some(answer), elapsed, None),
+            Err(e) => {
+                eprint!("{}", describe_error(e.as_ref(), &input, &lines_str[..]));
+                record(*part, None, elapsed, Some(e.to_string()))
+            },
+        });
+    }
+    records
+}
+
+// What every day's binary does: solve both parts of each input given on the command line.
+pub fn run_day(solution: &dyn Solution) {
+    run_day_with_args(solution, std::env::args().skip(1))
+}
+
+// For days that take some options of their own before handing the rest over.
+pub fn run_day_with_args(solution: &dyn Solution, args: impl Iterator<Item = String>) {
+    let options = match parse_day_args(args) {
+        Ok(Some(o)) => o,
+        Ok(None) => {
+            println!("{}", DAY_USAGE);
+            return
+        },
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, DAY_USAGE);
+            std::process::exit(2);
+        },
+    };
+    let sources = match input::expand_sources(&options.inputs[..]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        },
+    };
+
+    let mut records: Vec<Record> = Vec::new();
+    for (i, source) in sources.iter().enumerate() {
+        let source_records = solve_source(solution, source, &Part::ALL);
+        if options.format == Format::Text {
+            if sources.len() > 1 {
+                if i > 0 {
+                    println!();
+                }
+                println!("==> {} <==", source);
+            }
+            for answer in source_records.iter().filter_map(|r| r.answer.as_ref()) {
+                println!("{}", answer);
+            }
+        }
+        records.extend(source_records);
+    }
+    match options.format {
+        Format::Text => (),
+        Format::Json => println!("{}", report::render_json(&records[..])),
+        Format::Csv => println!("{}", report::render_csv(&records[..])),
+    }
+    if records.iter().any(|r| r.error.is_some()) {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ error::InputError, parse::ParseErrorKind };
+
+    #[test]
+    fn describe_parse_error() {
+        let lines = ["abc"];
+        let error: Box<dyn std::error::Error> = Box::new(ParseError::new(
+            ParseErrorKind::Missing { expected: String::from("a digit") },
+            1,
+            3,
+        ));
+        assert!(describe_error(error.as_ref(), "stdin", &lines).contains("1 | abc\n  | ^^^"));
+    }
+
+    #[test]
+    fn day_args() {
+        let parse = |s: &str| parse_day_args(s.split_whitespace().map(String::from));
+        let expected = DayOptions {
+            format: Format::Csv,
+            inputs: vec![String::from("a.txt"), String::from("-")],
+        };
+        assert_eq!(Ok(Some(expected)), parse("a.txt --format csv -"));
+        assert_eq!(Ok(None), parse("a.txt --help"));
+        assert!(parse("--format").is_err());
+        assert!(parse("--format yaml").is_err());
+        assert!(parse("--frobnicate").is_err());
+    }
+
+    #[test]
+    fn describe_other_error() {
+        let error: Box<dyn std::error::Error> = Box::new(InputError::Empty);
+        assert_eq!("error: Nothing found in the input!\n", describe_error(error.as_ref(), "stdin", &[]));
+    }
+}
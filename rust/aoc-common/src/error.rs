@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(std::io::Error),
+    Empty,
+    NoMatches(String),
+}
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Io(e) => write!(f, "Couldn't read input: {}", e),
+            InputError::Empty => write!(f, "Nothing found in the input!"),
+            InputError::NoMatches(pattern) => write!(f, "No files match \"{}\"", pattern),
+        }
+    }
+}
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io(e) => Some(e),
+            InputError::Empty | InputError::NoMatches(_) => None,
+        }
+    }
+}
+impl From<std::io::Error> for InputError {
+    fn from(e: std::io::Error) -> Self {
+        InputError::Io(e)
+    }
+}
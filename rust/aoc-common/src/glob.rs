@@ -0,0 +1,145 @@
+use std::path::{ Component, Path, PathBuf };
+
+pub fn is_pattern(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+// Parses a "[...]" class, starting just after the '['. Returns whether `c` is in
+// the class and how many pattern characters the class used, or None if it's never closed.
+fn match_class(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let negated = matches!(pattern.first(), Some('!') | Some('^'));
+    let mut i = if negated { 1 } else { 0 };
+    let mut found = false;
+    let mut first = true;
+    while i < pattern.len() {
+        if pattern[i] == ']' && !first {
+            return Some((found != negated, i + 1));
+        }
+        if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            found |= pattern[i] <= c && c <= pattern[i + 2];
+            i += 3;
+        }
+        else {
+            found |= pattern[i] == c;
+            i += 1;
+        }
+        first = false;
+    }
+    None
+}
+
+fn matches_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|i| matches_from(&pattern[1..], &name[i..])),
+        Some('?') => !name.is_empty() && matches_from(&pattern[1..], &name[1..]),
+        Some('[') => match (name.first(), match_class(&pattern[1..], name.first().copied().unwrap_or('\0'))) {
+            (Some(_), Some((true, used))) => matches_from(&pattern[(used + 1)..], &name[1..]),
+            (_, Some((false, _))) | (None, Some(_)) => false,
+            // An unclosed '[' is just a '['.
+            (_, None) => name.first() == Some(&'[') && matches_from(&pattern[1..], &name[1..]),
+        },
+        Some(c) => name.first() == Some(c) && matches_from(&pattern[1..], &name[1..]),
+    }
+}
+
+// Matches a single file name against a pattern using *, ? and [...] like a shell would,
+// so wildcards don't match a leading '.'.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches_from(&pattern[..], &name[..])
+}
+
+fn read_dir_or_current(dir: &Path) -> Vec<(String, PathBuf)> {
+    let to_read = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    let Ok(entries) = std::fs::read_dir(to_read) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .map(|name| (name.clone(), dir.join(name)))
+        .collect()
+}
+
+// Every directory below `dir`, including `dir` itself, for "**".
+fn descendant_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut to_return = vec![dir.to_path_buf()];
+    for (name, path) in read_dir_or_current(dir) {
+        if path.is_dir() && !name.starts_with('.') {
+            to_return.extend(descendant_dirs(&path));
+        }
+    }
+    to_return
+}
+
+// Expands a pattern into the sorted list of existing paths it matches.
+pub fn expand(pattern: &str) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        candidates = match component {
+            Component::Normal(part) if part == "**" => candidates
+                .iter()
+                .flat_map(|c| descendant_dirs(c))
+                .collect(),
+            Component::Normal(part) if is_pattern(&part.to_string_lossy()) => {
+                let part = part.to_string_lossy();
+                candidates
+                    .iter()
+                    .flat_map(|c| read_dir_or_current(c))
+                    .filter(|(name, _)| matches(&part, name))
+                    .map(|(_, path)| path)
+                    .collect()
+            },
+            other => candidates.iter().map(|c| c.join(other)).collect(),
+        };
+    }
+    let mut to_return: Vec<PathBuf> = candidates.into_iter().filter(|p| p.exists()).collect();
+    to_return.sort();
+    to_return.dedup();
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_names() {
+        assert!(matches("*.txt", "day1.txt"));
+        assert!(!matches("*.txt", "day1.txt.bak"));
+        assert!(matches("day?.txt", "day5.txt"));
+        assert!(!matches("day?.txt", "day10.txt"));
+        assert!(matches("day[1-3].txt", "day2.txt"));
+        assert!(!matches("day[1-3].txt", "day4.txt"));
+        assert!(matches("day[!1-3].txt", "day4.txt"));
+        assert!(matches("[]].txt", "].txt"));
+        assert!(matches("a[b", "a[b"));
+        assert!(!matches("*", ".hidden"));
+        assert!(matches(".*", ".hidden"));
+        assert!(matches("*", ""));
+    }
+
+    #[test]
+    fn expand_in_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-common-glob-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["day1.txt", "day2.txt", "notes.md", "nested/day3.txt"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        let base = dir.to_string_lossy();
+
+        let found = expand(&format!("{}/day*.txt", base));
+        assert_eq!(vec![dir.join("day1.txt"), dir.join("day2.txt")], found);
+
+        let found = expand(&format!("{}/**/day*.txt", base));
+        assert_eq!(vec![dir.join("day1.txt"), dir.join("day2.txt"), dir.join("nested/day3.txt")], found);
+
+        assert!(expand(&format!("{}/*.csv", base)).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,287 @@
+use std::{ fmt::Display, ops::{ Index, IndexMut } };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+impl Point {
+    pub fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+    // None if the move would go below zero.
+    pub fn offset(&self, dx: isize, dy: isize) -> Option<Point> {
+        Some(Point { x: self.x.checked_add_signed(dx)?, y: self.y.checked_add_signed(dy)? })
+    }
+}
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// Right, down, left, up.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+// Clockwise from the right.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+// A rectangle of cells stored row by row, with (0, 0) at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+impl<T> Grid<T> {
+    // None unless the cells make whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        match (width, cells.len()) {
+            (0, 0) => Some(Grid { cells, width: 0, height: 0 }),
+            (0, _) => None,
+            (w, len) if len % w == 0 => Some(Grid { height: len / w, cells, width }),
+            _ => None,
+        }
+    }
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| Point::new(x, y))).map(&mut f).collect();
+        Grid { cells, width, height }
+    }
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn cells(&self) -> &[T] {
+        &self.cells[..]
+    }
+
+    pub fn in_bounds(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+    pub fn index_of(&self, point: Point) -> Option<usize> {
+        self.in_bounds(point).then(|| point.x + point.y * self.width)
+    }
+    pub fn point_of(&self, index: usize) -> Option<Point> {
+        (index < self.cells.len()).then(|| Point::new(index % self.width, index / self.width))
+    }
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    // The points next to `point` that are in the grid, in the order of `directions`.
+    pub fn neighbours<'a>(&self, point: Point, directions: &'a [(isize, isize)]) -> impl Iterator<Item = Point> + 'a {
+        let (width, height) = (self.width, self.height);
+        directions
+            .iter()
+            .filter_map(move |(dx, dy)| point.offset(*dx, *dy))
+            .filter(move |p| p.x < width && p.y < height)
+    }
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbours(point, &ORTHOGONAL)
+    }
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbours(point, &ALL_DIRECTIONS)
+    }
+
+    // Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let step = self.width.max(1);
+        let skip = if x < self.width { x } else { self.cells.len() };
+        self.cells.iter().skip(skip).step_by(step)
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+    // The cells from `from` to `to`, both included, row by row. Parts outside the grid are left out.
+    pub fn region(&self, from: Point, to: Point) -> impl Iterator<Item = (Point, &T)> {
+        let xs = from.x..(to.x + 1).min(self.width);
+        (from.y..(to.y + 1).min(self.height))
+            .flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+            .map(|p| (p, &self[p]))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+    fn rearranged(&self, width: usize, height: usize, from: impl Fn(Point) -> Point) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(width, height, |p| self[from(p)].clone())
+    }
+    // Rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.height, self.width, |p| Point::new(p.y, height - 1 - p.x))
+    }
+    pub fn rotate_anticlockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.height, self.width, |p| Point::new(width - 1 - p.y, p.x))
+    }
+    // Left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.width, self.height, |p| Point::new(width - 1 - p.x, p.y))
+    }
+    // Top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.width, self.height, |p| Point::new(p.x, height - 1 - p.y))
+    }
+}
+impl Grid<char> {
+    // None unless every line is as long as the first.
+    pub fn from_lines(lines: &[&str]) -> Option<Grid<char>> {
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        if lines.iter().any(|l| l.chars().count() != width) {
+            return None;
+        }
+        Grid::from_vec(width, lines.iter().flat_map(|l| l.chars()).collect())
+    }
+}
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(i) => &self.cells[i],
+            None => panic!("{} is outside a {}x{} grid", point, self.width, self.height),
+        }
+    }
+}
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{} is outside a {}x{} grid", point, self.width, self.height),
+        }
+    }
+}
+// One line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(lines: &[&str]) -> Grid<char> {
+        Grid::from_lines(lines).unwrap()
+    }
+
+    #[test]
+    fn construction() {
+        assert_eq!(None, Grid::from_vec(3, vec![1, 2, 3, 4]));
+        assert_eq!(None, Grid::from_lines(&["abc", "de"]));
+        let g = Grid::from_vec(2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!((2, 3), (g.width(), g.height()));
+        assert_eq!(g, Grid::from_fn(2, 3, |p| p.x + 2 * p.y + 1));
+        assert!(Grid::<u8>::from_vec(0, Vec::new()).unwrap().is_empty());
+        assert_eq!(vec!['x'; 4], Grid::filled(2, 2, 'x').cells());
+    }
+
+    #[test]
+    fn access() {
+        let mut g = grid(&["abc", "def"]);
+        assert_eq!(Some(&'f'), g.get(Point::new(2, 1)));
+        assert_eq!(None, g.get(Point::new(3, 0)));
+        assert_eq!(None, g.get(Point::new(0, 2)));
+        *g.get_mut(Point::new(0, 1)).unwrap() = 'x';
+        g[Point::new(1, 1)] = 'y';
+        assert_eq!("abc\nxyf\n", g.to_string());
+        assert_eq!(Some(5), g.index_of(Point::new(2, 1)));
+        assert_eq!(Some(Point::new(2, 1)), g.point_of(5));
+        assert_eq!(None, g.point_of(6));
+        assert_eq!(None, Point::new(0, 3).offset(-1, 0));
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = grid(&["abc", "def", "ghi"]);
+        let letters = |points: Vec<Point>| points.into_iter().map(|p| g[p]).collect::<String>();
+        assert_eq!("fhdb", letters(g.neighbours4(Point::new(1, 1)).collect()));
+        assert_eq!("fihgdabc", letters(g.neighbours8(Point::new(1, 1)).collect()));
+        assert_eq!("bed", letters(g.neighbours8(Point::new(0, 0)).collect()));
+        assert_eq!("hf", letters(g.neighbours4(Point::new(2, 2)).collect()));
+    }
+
+    #[test]
+    fn iterators() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!(vec!["abc", "def"], g.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<String>>());
+        assert_eq!(Some(&['d', 'e', 'f'][..]), g.row(1));
+        assert_eq!(None, g.row(2));
+        let columns: Vec<String> = g.columns().map(|c| c.collect()).collect();
+        assert_eq!(vec!["ad", "be", "cf"], columns);
+        assert_eq!(0, g.column(3).count());
+        assert_eq!(Some((Point::new(2, 0), &'c')), g.iter().nth(2));
+        let region: String = g.region(Point::new(1, 0), Point::new(5, 5)).map(|(_, c)| c).collect();
+        assert_eq!("bcef", region);
+    }
+
+    #[test]
+    fn transformations() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!(grid(&["ad", "be", "cf"]), g.transpose());
+        assert_eq!(grid(&["da", "eb", "fc"]), g.rotate_clockwise());
+        assert_eq!(grid(&["cf", "be", "ad"]), g.rotate_anticlockwise());
+        assert_eq!(grid(&["cba", "fed"]), g.flip_horizontal());
+        assert_eq!(grid(&["def", "abc"]), g.flip_vertical());
+        assert_eq!(g, g.rotate_clockwise().rotate_anticlockwise());
+        assert_eq!(g, g.rotate_clockwise().rotate_clockwise().rotate_clockwise().rotate_clockwise());
+        assert_eq!(g.map(|c| c.to_ascii_uppercase()), grid(&["ABC", "DEF"]));
+    }
+}
@@ -0,0 +1,101 @@
+use std::{ fmt::Display, fs::File, io::{ BufRead, BufReader }, path::PathBuf };
+
+use crate::{ error::InputError, glob };
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+impl Source {
+    pub fn read_lines(&self) -> Result<Vec<String>, InputError> {
+        match self {
+            Source::Stdin => read_lines(std::io::stdin().lock()),
+            Source::File(path) => read_lines(BufReader::new(File::open(path)?)),
+        }
+    }
+    // For reading a line at a time rather than all at once.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            Source::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+}
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// Turns command line inputs into sources: "-" is stdin, patterns are expanded
+// (a pattern matching nothing is an error), and anything else is taken as a path.
+// No inputs at all means stdin.
+pub fn expand_sources(args: &[String]) -> Result<Vec<Source>, InputError> {
+    if args.is_empty() {
+        return Ok(vec![Source::Stdin]);
+    }
+    let mut sources: Vec<Source> = Vec::new();
+    for arg in args {
+        if arg == "-" {
+            sources.push(Source::Stdin);
+        }
+        else if glob::is_pattern(arg) {
+            let paths = glob::expand(arg);
+            if paths.is_empty() {
+                return Err(InputError::NoMatches(arg.clone()));
+            }
+            sources.extend(paths.into_iter().map(Source::File));
+        }
+        else {
+            sources.push(Source::File(PathBuf::from(arg)));
+        }
+    }
+    Ok(sources)
+}
+
+pub fn read_lines<R: BufRead>(reader: R) -> Result<Vec<String>, InputError> {
+    let lines: Vec<String> = reader
+        .lines()
+        .collect::<Result<_, _>>()?;
+    if lines.is_empty() {
+        return Err(InputError::Empty);
+    }
+    Ok(lines)
+}
+
+pub fn as_strs(lines: &[String]) -> Vec<&str> {
+    lines.iter().map(|s| s.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_lines() {
+        let input = "first\nsecond\n\nfourth";
+        let lines = read_lines(input.as_bytes()).unwrap();
+        assert_eq!(vec!["first", "second", "", "fourth"], as_strs(&lines[..]));
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(matches!(read_lines("".as_bytes()), Err(InputError::Empty)));
+    }
+
+    #[test]
+    fn sources_from_args() {
+        assert_eq!(vec![Source::Stdin], expand_sources(&[]).unwrap());
+        let args = [String::from("-"), String::from("example.txt")];
+        assert_eq!(
+            vec![Source::Stdin, Source::File(PathBuf::from("example.txt"))],
+            expand_sources(&args).unwrap(),
+        );
+        let args = [String::from("definitely/not/here/*.txt")];
+        assert!(matches!(expand_sources(&args), Err(InputError::NoMatches(_))));
+    }
+}
@@ -0,0 +1,21 @@
+pub mod cli;
+pub mod error;
+pub mod glob;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod rng;
+pub mod slice;
+pub mod solution;
+pub mod vec_map;
+
+pub use error::InputError;
+pub use grid::{ Grid, Point };
+pub use input::Source;
+pub use parse::{ ParseError, ParseErrorKind };
+pub use report::{ Format, Record };
+pub use rng::Rng;
+pub use slice::all_equal;
+pub use solution::{ Answer, ParseResult, Part, Solution, SolveResult };
+pub use vec_map::VecMap;
@@ -0,0 +1,210 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    // Something was there, but not what we wanted.
+    Expected { expected: String, found: String },
+    // We ran out of text before finding what we wanted.
+    Missing { expected: String },
+    InvalidNumber { text: String, reason: String },
+    // The text was well-formed but doesn't make sense for the puzzle.
+    Invalid(String),
+}
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Expected { expected, found } => write!(f, "expected {}, found \"{}\"", expected, found),
+            ParseErrorKind::Missing { expected } => write!(f, "missing {}", expected),
+            ParseErrorKind::InvalidNumber { text, reason } => write!(f, "invalid number \"{}\": {}", text, reason),
+            ParseErrorKind::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Lines and columns both start at 1, and columns count characters rather than bytes.
+// Parsers of a single line leave `line` as 1 and let the caller move it with `on_line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+    pub kind: ParseErrorKind,
+}
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, column: usize, len: usize) -> ParseError {
+        ParseError { line: 1, column, len: len.max(1), kind }
+    }
+    // Points at `part`, which must be a subslice of `line`.
+    pub fn at(line: &str, part: &str, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, column_of(line, part), part.chars().count())
+    }
+    // Points just past the end of `part`, for when something should have followed it.
+    pub fn after(line: &str, part: &str, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, column_of(line, part) + part.chars().count(), 1)
+    }
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+    // For errors from parsers that were only given a piece of a line.
+    pub fn shifted(mut self, columns: usize) -> ParseError {
+        self.column += columns;
+        self
+    }
+
+    // Renders the error the way a compiler would, quoting the offending line.
+    pub fn diagnostic(&self, source_name: &str, lines: &[&str]) -> String {
+        self.render(source_name, lines.get(self.line - 1).copied())
+    }
+    // The same, for when only the offending line was kept.
+    pub fn line_diagnostic(&self, source_name: &str, text: &str) -> String {
+        self.render(source_name, Some(text))
+    }
+    fn render(&self, source_name: &str, text: Option<&str>) -> String {
+        let mut to_return = format!("error: {}\n", self.kind);
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        to_return.push_str(&format!("{}--> {}:{}:{}\n", gutter, source_name, self.line, self.column));
+        if let Some(text) = text {
+            to_return.push_str(&format!("{} |\n", gutter));
+            to_return.push_str(&format!("{} | {}\n", line_number, text));
+            to_return.push_str(&format!(
+                "{} | {}{}\n",
+                gutter,
+                " ".repeat(self.column - 1),
+                "^".repeat(self.len),
+            ));
+        }
+        to_return
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+impl std::error::Error for ParseError {}
+
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    debug_assert!(offset <= line.len(), "part must be a subslice of line");
+    line[..offset.min(line.len())].chars().count() + 1
+}
+
+pub fn expected(line: &str, found: &str, expected: &str) -> ParseError {
+    ParseError::at(line, found, ParseErrorKind::Expected {
+        expected: String::from(expected),
+        found: String::from(found),
+    })
+}
+
+pub fn missing_after(line: &str, part: &str, expected: &str) -> ParseError {
+    ParseError::after(line, part, ParseErrorKind::Missing { expected: String::from(expected) })
+}
+
+pub fn invalid(line: &str, part: &str, message: String) -> ParseError {
+    ParseError::at(line, part, ParseErrorKind::Invalid(message))
+}
+
+// Returns what follows `prefix` in `s`.
+pub fn expect_prefix<'a>(line: &str, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    match s.strip_prefix(prefix) {
+        Some(rest) => Ok(rest),
+        None => {
+            let found_len = s.char_indices().nth(prefix.chars().count()).map_or(s.len(), |(i, _)| i);
+            Err(expected(line, &s[..found_len], &format!("\"{}\"", prefix.trim())))
+        },
+    }
+}
+
+pub fn split_once<'a>(line: &str, s: &'a str, delimiter: char) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter).ok_or_else(|| missing_after(line, s, &format!("'{}'", delimiter)))
+}
+
+pub fn parse_number<T>(line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if token.is_empty() {
+        return Err(missing_after(line, token, "a number"));
+    }
+    token.parse().map_err(|e: T::Err| ParseError::at(line, token, ParseErrorKind::InvalidNumber {
+        text: String::from(token),
+        reason: e.to_string(),
+    }))
+}
+
+// Parses whitespace separated numbers.
+pub fn numbers<T>(line: &str, s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.split_whitespace().map(|token| parse_number(line, token)).collect()
+}
+
+// Applies a single line parser to every line, numbering any error.
+pub fn parse_lines<T>(
+    lines: &[&str],
+    parser: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| parser(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let line = "Game 12: 3 blue";
+        assert_eq!(1, column_of(line, line));
+        assert_eq!(6, column_of(line, &line[5..7]));
+        assert_eq!(16, column_of(line, &line[15..]));
+    }
+
+    #[test]
+    fn helpers() {
+        let line = "Card 1: 41 x8 | 83";
+        assert_eq!(Ok(" 1: 41 x8 | 83"), expect_prefix(line, line, "Card"));
+        let (card, numbers_part) = split_once(line, line, ':').unwrap();
+        assert_eq!("Card 1", card);
+        let error = numbers::<usize>(line, numbers_part).unwrap_err();
+        assert_eq!(12, error.column);
+        assert_eq!(2, error.len);
+        assert!(matches!(error.kind, ParseErrorKind::InvalidNumber { .. }));
+
+        let error = expect_prefix(line, line, "Game ").unwrap_err();
+        assert_eq!(expected(line, &line[..5], "\"Game\""), error);
+
+        let error = split_once(line, &line[8..10], ';').unwrap_err();
+        assert_eq!(11, error.column);
+    }
+
+    #[test]
+    fn parse_lines_numbers_lines() {
+        let lines = ["1 2", "3 4", "5 six"];
+        let error = parse_lines(&lines, |l| numbers::<usize>(l, l)).unwrap_err();
+        assert_eq!(3, error.line);
+        assert_eq!(3, error.column);
+    }
+
+    #[test]
+    fn diagnostic() {
+        let lines = ["Game 1: 3 blue", "Game 2: x1 red"];
+        let error = parse_number::<usize>(lines[1], &lines[1][8..10]).unwrap_err().on_line(2);
+        let expected = "error: invalid number \"x1\": invalid digit found in string
+ --> input.txt:2:9
+  |
+2 | Game 2: x1 red
+  |         ^^
+";
+        assert_eq!(expected, error.diagnostic("input.txt", &lines));
+        assert_eq!(expected, error.line_diagnostic("input.txt", lines[1]));
+    }
+}
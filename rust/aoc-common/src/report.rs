@@ -0,0 +1,171 @@
+use std::{ fmt::Display, str::FromStr, time::Duration };
+
+use crate::{ Answer, Part };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatParseError(pub String);
+impl Display for FormatParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" is not a format, expected json, csv or text", self.0)
+    }
+}
+impl std::error::Error for FormatParseError {}
+
+impl FromStr for Format {
+    type Err = FormatParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(FormatParseError(String::from(s))),
+        }
+    }
+}
+
+// The outcome of running one part on one input. Exactly one of answer and error is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+impl Record {
+    pub fn elapsed_ms(&self) -> String {
+        format!("{:.3}", self.elapsed.as_secs_f64() * 1000.0)
+    }
+    pub fn to_json(&self) -> String {
+        let optional = |s: &Option<String>| s.as_ref().map_or(String::from("null"), |s| json_string(s));
+        format!(
+            "{{\"day\":{},\"part\":{},\"input\":{},\"answer\":{},\"elapsed_ms\":{},\"error\":{}}}",
+            self.day,
+            self.part,
+            json_string(&self.input),
+            optional(&self.answer),
+            self.elapsed_ms(),
+            optional(&self.error),
+        )
+    }
+    pub fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(&self.input),
+            csv_field(self.answer.as_deref().unwrap_or("")),
+            self.elapsed_ms(),
+            csv_field(self.error.as_deref().unwrap_or("")),
+        ].join(",")
+    }
+}
+
+pub const CSV_HEADER: &str = "day,part,input,answer,elapsed_ms,error";
+
+pub fn json_string(s: &str) -> String {
+    let mut to_return = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => to_return.push_str("\\\""),
+            '\\' => to_return.push_str("\\\\"),
+            '\n' => to_return.push_str("\\n"),
+            '\r' => to_return.push_str("\\r"),
+            '\t' => to_return.push_str("\\t"),
+            c if (c as u32) < 0x20 => to_return.push_str(&format!("\\u{:04x}", c as u32)),
+            c => to_return.push(c),
+        }
+    }
+    to_return.push('"');
+    to_return
+}
+
+// Quotes a field only when it has to be, doubling any quotes inside it.
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    }
+    else {
+        String::from(s)
+    }
+}
+
+// A JSON array with one record per line.
+pub fn render_json(records: &[Record]) -> String {
+    if records.is_empty() {
+        return String::from("[]");
+    }
+    let rows: Vec<String> = records.iter().map(|r| format!("  {}", r.to_json())).collect();
+    format!("[\n{}\n]", rows.join(",\n"))
+}
+
+pub fn render_csv(records: &[Record]) -> String {
+    let mut lines: Vec<String> = vec![String::from(CSV_HEADER)];
+    lines.extend(records.iter().map(|r| r.to_csv()));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: Part::One,
+                input: String::from("inputs/day1.txt"),
+                answer: Some(String::from("54388")),
+                elapsed: Duration::from_micros(1500),
+                error: None,
+            },
+            Record {
+                day: 1,
+                part: Part::Two,
+                input: String::from("odd, \"name\".txt"),
+                answer: None,
+                elapsed: Duration::from_micros(20),
+                error: Some(String::from("line 1, column 1: missing a digit")),
+            },
+        ]
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Ok(Format::Json), "JSON".parse());
+        assert_eq!(Ok(Format::Csv), "csv".parse());
+        assert_eq!(Ok(Format::Text), "text".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn escape_json() {
+        assert_eq!("\"a\\\"b\\\\c\\nd\\u0001\"", json_string("a\"b\\c\nd\u{1}"));
+    }
+
+    #[test]
+    fn json_records() {
+        let expected = "[
+  {\"day\":1,\"part\":1,\"input\":\"inputs/day1.txt\",\"answer\":\"54388\",\"elapsed_ms\":1.500,\"error\":null},
+  {\"day\":1,\"part\":2,\"input\":\"odd, \\\"name\\\".txt\",\"answer\":null,\"elapsed_ms\":0.020,\"error\":\"line 1, column 1: missing a digit\"}
+]";
+        assert_eq!(expected, render_json(&records()));
+        assert_eq!("[]", render_json(&[]));
+    }
+
+    #[test]
+    fn csv_records() {
+        let expected = "day,part,input,answer,elapsed_ms,error
+1,1,inputs/day1.txt,54388,1.500,
+1,2,\"odd, \"\"name\"\".txt\",,0.020,\"line 1, column 1: missing a digit\"";
+        assert_eq!(expected, render_csv(&records()));
+    }
+}
@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+// SplitMix64: tiny, fast, and good enough for making up puzzle inputs.
+// The same seed always gives the same numbers, on every platform.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rng {
+    state: u64,
+}
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    // Uniform in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(!range.is_empty(), "can't pick from an empty range");
+        let width = (range.end - range.start) as u128;
+        range.start + ((self.next_u64() as u128 * width) >> 64) as usize
+    }
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..(i + 1)));
+        }
+    }
+    // `count` different numbers from `range`, in a random order.
+    pub fn distinct(&mut self, range: Range<usize>, count: usize) -> Vec<usize> {
+        let mut pool: Vec<usize> = range.collect();
+        assert!(count <= pool.len(), "can't pick {} different numbers from {}", count, pool.len());
+        self.shuffle(&mut pool[..]);
+        pool.truncate(count);
+        pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(2023);
+        let mut b = Rng::new(2023);
+        let from_a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let from_b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        assert_eq!(from_a, from_b);
+        assert_ne!(from_a[0], Rng::new(2024).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let n = rng.range(10..16);
+            assert!((10..16).contains(&n));
+            seen[n - 10] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+        assert_eq!(7, rng.range(7..8));
+        let f = rng.next_f64();
+        assert!((0.0..1.0).contains(&f));
+    }
+
+    #[test]
+    fn shuffles_and_picks() {
+        let mut rng = Rng::new(5);
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items[..]);
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!((0..20).collect::<Vec<usize>>(), sorted);
+
+        let picked = rng.distinct(1..100, 10);
+        let mut unique = picked.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(10, unique.len());
+        assert!(picked.iter().all(|n| (1..100).contains(n)));
+    }
+}
@@ -0,0 +1,22 @@
+pub fn all_equal<A>(slice: &[A]) -> bool
+where
+    A: PartialEq
+{
+    if slice.is_empty() {
+        return false
+    }
+    slice.iter().all(|a| a == &slice[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_equal_slices() {
+        assert!(all_equal(&[3, 3, 3]));
+        assert!(all_equal(&[0]));
+        assert!(!all_equal(&[1, 2, 1]));
+        assert!(!all_equal::<usize>(&[]));
+    }
+}
@@ -0,0 +1,100 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::rng::Rng;
+
+pub type Answer = String;
+pub type SolveResult = Result<Answer, Box<dyn std::error::Error>>;
+pub type ParseResult = Result<(), Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartParseError(pub String);
+impl Display for PartParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" is not a part, expected 1 or 2", self.0)
+    }
+}
+impl std::error::Error for PartParseError {}
+
+impl FromStr for Part {
+    type Err = PartParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(PartParseError(String::from(s))),
+        }
+    }
+}
+
+// Every day implements this so that the runner can treat them all the same way.
+pub trait Solution {
+    fn day(&self) -> u8;
+    // Just the parsing that both parts share, so it can be timed on its own.
+    fn parse(&self, input: &[&str]) -> ParseResult;
+    fn part1(&self, input: &[&str]) -> SolveResult;
+    fn part2(&self, input: &[&str]) -> SolveResult;
+    // A random but valid input of roughly `size` lines (or races, or rows...), for stress testing.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String>;
+
+    fn solve(&self, part: Part, input: &[&str]) -> SolveResult {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+    impl Solution for Echo {
+        fn day(&self) -> u8 {
+            0
+        }
+        fn parse(&self, _input: &[&str]) -> ParseResult {
+            Ok(())
+        }
+        fn part1(&self, input: &[&str]) -> SolveResult {
+            Ok(input.len().to_string())
+        }
+        fn part2(&self, input: &[&str]) -> SolveResult {
+            Ok(input.concat())
+        }
+        fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+            (0..size).map(|_| rng.range(0..10).to_string()).collect()
+        }
+    }
+
+    #[test]
+    fn solve_dispatches_on_part() {
+        let input = ["ab", "cd"];
+        assert_eq!("2", Echo.solve(Part::One, &input).unwrap());
+        assert_eq!("abcd", Echo.solve(Part::Two, &input).unwrap());
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), " 2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,79 @@
+// A small map backed by a Vec, for when keys are few and only need PartialEq.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VecMap<K, V> {
+    pub vec: Vec<(K, V)>
+}
+impl<K, V> Default for VecMap<K, V> {
+    fn default() -> Self {
+        VecMap { vec: Vec::new() }
+    }
+}
+impl<K, V> VecMap<K, V> {
+    pub fn new() -> VecMap<K, V>
+    where
+        K: PartialEq,
+    {
+        VecMap { vec: Vec::new() }
+    }
+    pub fn from_tuples_slice(tuples: &[(K, V)]) -> VecMap<K, V>
+    where
+        K: Clone,
+        K: PartialEq,
+        V: Clone,
+    {
+        VecMap { vec: Vec::from(tuples) }
+    }
+    pub fn get_value(&self, key: &K) -> Option<&V>
+    where
+        K: PartialEq,
+    {
+        self.get_id_from_key(key).map(|i| &self.vec[i].1)
+    }
+    fn get_id_from_key(&self, key: &K) -> Option<usize>
+    where
+        K: PartialEq,
+    {
+        self.vec.iter().position(|tuple| &tuple.0 == key)
+    }
+    pub fn update(&mut self, tuple: (K, V))
+    where
+        K: PartialEq
+    {
+        match self.get_id_from_key(&tuple.0) {
+            Some(i) => self.vec[i].1 = tuple.1,
+            None => self.vec.push(tuple),
+        }
+    }
+    pub fn update_with_fn(&mut self, key: K, identity: V, func: impl FnOnce(&V) -> V)
+    where
+        K: PartialEq
+    {
+        match self.get_id_from_key(&key) {
+            Some(i) => self.vec[i].1 = func(&self.vec[i].1),
+            None => self.update((key, identity)),
+        }
+    }
+    pub fn keys(&self) -> Vec<&K> {
+        self.vec.iter().map(|tuple| &tuple.0).collect()
+    }
+    pub fn values(&self) -> Vec<&V> {
+        self.vec.iter().map(|tuple| &tuple.1).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let mut vec_map: VecMap<char, usize> = VecMap::new();
+        for c in "abacab".chars() {
+            vec_map.update_with_fn(c, 1, |n| n + 1);
+        }
+        assert_eq!(Some(&3), vec_map.get_value(&'a'));
+        assert_eq!(Some(&2), vec_map.get_value(&'b'));
+        assert_eq!(None, vec_map.get_value(&'d'));
+        assert_eq!(vec![&'a', &'b', &'c'], vec_map.keys());
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::{ Path, PathBuf };
+
+use aoc_common::{ parse, Answer, ParseError, Part };
+
+// Known-good answers for one input, kept in a small TOML file:
+//
+//     part1 = "54388"
+//     part2 = 53515
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+    pub fn render(&self) -> String {
+        Part::ALL
+            .iter()
+            .filter_map(|part| self.get(*part).map(|a| format!("part{} = \"{}\"\n", part, escape(a))))
+            .collect()
+    }
+}
+
+// inputs/day5.txt keeps its answers in inputs/day5.answers.toml.
+pub fn path_for(input: &Path) -> PathBuf {
+    input.with_extension("answers.toml")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn parse_value(line: &str, value: &str) -> Result<Answer, ParseError> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut to_return = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    let rest = chars.as_str().trim();
+                    if !rest.is_empty() && !rest.starts_with('#') {
+                        return Err(parse::expected(line, rest, "the end of the line"));
+                    }
+                    return Ok(to_return);
+                },
+                '\\' => match chars.next() {
+                    Some(escaped @ ('"' | '\\')) => to_return.push(escaped),
+                    _ => return Err(parse::invalid(line, value, String::from("only \\\" and \\\\ can be escaped"))),
+                },
+                c => to_return.push(c),
+            }
+        }
+        return Err(parse::missing_after(line, value, "a closing '\"'"));
+    }
+    let value = value.split('#').next().unwrap_or("").trim_end();
+    if value.is_empty() {
+        return Err(parse::missing_after(line, value, "an answer"));
+    }
+    // Bare values have to be integers, as in TOML.
+    parse::parse_number::<i64>(line, value)?;
+    Ok(String::from(value))
+}
+
+pub fn parse_answers(lines: &[&str]) -> Result<Answers, ParseError> {
+    let mut answers = Answers::default();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let parse_line = || -> Result<(Part, Answer), ParseError> {
+            let (key, value) = parse::split_once(line, trimmed, '=')?;
+            let key = key.trim();
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(parse::expected(line, key, "part1 or part2")),
+            };
+            if answers.get(part).is_some() {
+                return Err(parse::invalid(line, key, format!("{} is given twice", key)));
+            }
+            Ok((part, parse_value(line, value.trim())?))
+        };
+        let (part, answer) = parse_line().map_err(|e| e.on_line(i + 1))?;
+        answers.set(part, answer);
+    }
+    Ok(answers)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+pub fn verdict(expected: Option<&Answer>, answer: &str) -> Verdict {
+    match expected {
+        None => Verdict::Missing,
+        Some(e) if e == answer => Verdict::Pass,
+        Some(e) => Verdict::Fail { expected: e.clone() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let lines = [
+            "# day 7",
+            "part1 = \"6440\"  # the example",
+            "",
+            "  part2=5905",
+        ];
+        let expected = Answers { part1: Some(String::from("6440")), part2: Some(String::from("5905")) };
+        assert_eq!(Ok(expected), parse_answers(&lines));
+        assert_eq!(Ok(Answers::default()), parse_answers(&[]));
+    }
+
+    #[test]
+    fn render_round_trip() {
+        let answers = Answers { part1: None, part2: Some(String::from("a \"quoted\" \\ answer")) };
+        let rendered = answers.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(Ok(answers), parse_answers(&lines[..]));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(2, parse_answers(&["part1 = 1", "part3 = 2"]).unwrap_err().line);
+        assert!(parse_answers(&["part1 = 1", "part1 = 2"]).is_err());
+        assert!(parse_answers(&["part1 = \"unclosed"]).is_err());
+        assert!(parse_answers(&["part1 = \"1\" 2"]).is_err());
+        assert!(parse_answers(&["part1 = twelve"]).is_err());
+        assert!(parse_answers(&["part1 = "]).is_err());
+        assert!(parse_answers(&["part1 \"1\""]).is_err());
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(PathBuf::from("inputs/day5.answers.toml"), path_for(Path::new("inputs/day5.txt")));
+    }
+
+    #[test]
+    fn verdicts() {
+        let expected = String::from("42");
+        assert_eq!(Verdict::Pass, verdict(Some(&expected), "42"));
+        assert_eq!(Verdict::Fail { expected: expected.clone() }, verdict(Some(&expected), "41"));
+        assert_eq!(Verdict::Missing, verdict(None, "42"));
+    }
+}
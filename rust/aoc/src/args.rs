@@ -0,0 +1,379 @@
+use std::{fmt::Display, path::PathBuf};
+
+use aoc_common::{ Format, Part };
+
+pub const USAGE: &str = "Usage:
+    aoc run --day <N> [--part <1|2>] [--format <text|json|csv>] [INPUT...]
+    aoc run --all [--part <1|2>] [--format <text|json|csv>] [--inputs <DIR>]
+    aoc bench --day <N> [--iterations <N>] [--inputs <DIR>] [--save <FILE>] [--baseline <FILE>] [--threshold <PERCENT>] [INPUT...]
+    aoc bench --all [--iterations <N>] [--inputs <DIR>] [--save <FILE>] [--baseline <FILE>] [--threshold <PERCENT>]
+    aoc verify [--day <N>] [--inputs <DIR>] [--record]
+    aoc new <N> [--root <DIR>]
+    aoc generate --day <N> [--seed <SEED>] [--size <N>] [--output <FILE>]
+    aoc list
+
+Each INPUT is a file, a glob pattern such as \"inputs/day1*.txt\", or - for stdin. INPUT defaults to stdin. With --all, each day reads <DIR>/day<N>.txt, where DIR defaults to \"inputs\".
+
+bench times each day's parse step and both parts separately over --iterations runs (10 by default), reading <DIR>/day<N>.txt unless INPUT is given.
+--save writes the medians to FILE, and --baseline compares against a saved FILE, failing if anything got slower by more than --threshold percent (10 by default).
+
+verify solves every day (or just --day) on <DIR>/day<N>.txt and checks the answers against <DIR>/day<N>.answers.toml.
+--record saves the answers that aren't in that file yet.
+
+new creates a day<N> crate with stubbed parts and registers it with the workspace and the runner.
+The workspace is found by looking up from the current directory, unless --root is given.
+
+generate makes up a valid input for a day, of roughly --size lines (100 by default), and prints it or writes it to --output.
+The same --seed always gives the same input. Without one, a seed is picked and printed to stderr.
+Day 6 is capped at three races, because its part 2 tries every way of holding the button in all of them glued together.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Days {
+    One(u8),
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunOptions {
+    pub days: Days,
+    pub parts: Vec<Part>,
+    pub inputs: Vec<String>,
+    pub inputs_dir: PathBuf,
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    pub days: Days,
+    pub inputs: Vec<String>,
+    pub inputs_dir: PathBuf,
+    pub iterations: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyOptions {
+    pub days: Days,
+    pub inputs_dir: PathBuf,
+    pub record: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOptions {
+    pub day: u8,
+    pub root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateOptions {
+    pub day: u8,
+    pub seed: Option<u64>,
+    pub size: usize,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    New(NewOptions),
+    Generate(GenerateOptions),
+    List,
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgsError(pub String);
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::error::Error for ArgsError {}
+
+fn value_of(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<String, ArgsError> {
+    args.next().ok_or_else(|| ArgsError(format!("{} needs a value", flag)))
+}
+
+fn parse_day(value: &str) -> Result<u8, ArgsError> {
+    value.parse().map_err(|_| ArgsError(format!("\"{}\" is not a day", value)))
+}
+
+fn days_from(day: Option<u8>, all: bool) -> Result<Days, ArgsError> {
+    match (day, all) {
+        (Some(_), true) => Err(ArgsError(String::from("--day and --all can't be used together"))),
+        (None, false) => Err(ArgsError(String::from("Either --day or --all must be given"))),
+        (Some(d), false) => Ok(Days::One(d)),
+        (None, true) => Ok(Days::All),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunOptions, ArgsError> {
+    let mut day: Option<u8> = None;
+    let mut all = false;
+    let mut parts: Vec<Part> = Part::ALL.to_vec();
+    let mut inputs: Vec<String> = Vec::new();
+    let mut inputs_dir = PathBuf::from("inputs");
+    let mut format = Format::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(parse_day(&value_of(&arg, &mut args)?)?);
+            },
+            "--part" | "-p" => {
+                let value = value_of(&arg, &mut args)?;
+                parts = vec![value.parse().map_err(|e| ArgsError(format!("{}", e)))?];
+            },
+            "--all" | "-a" => all = true,
+            "--format" | "-f" => {
+                let value = value_of(&arg, &mut args)?;
+                format = value.parse().map_err(|e| ArgsError(format!("{}", e)))?;
+            },
+            "--inputs" => inputs_dir = PathBuf::from(value_of(&arg, &mut args)?),
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(ArgsError(format!("Unknown option {}", flag)))
+            },
+            input => inputs.push(String::from(input)),
+        }
+    }
+    let days = days_from(day, all)?;
+    if days == Days::All && !inputs.is_empty() {
+        return Err(ArgsError(String::from("--all reads its inputs from --inputs, not from INPUT")))
+    }
+    Ok(RunOptions { days, parts, inputs, inputs_dir, format })
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchOptions, ArgsError> {
+    let mut day: Option<u8> = None;
+    let mut all = false;
+    let mut inputs: Vec<String> = Vec::new();
+    let mut inputs_dir = PathBuf::from("inputs");
+    let mut iterations: usize = 10;
+    let mut save: Option<PathBuf> = None;
+    let mut baseline: Option<PathBuf> = None;
+    let mut threshold: f64 = 10.0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_day(&value_of(&arg, &mut args)?)?),
+            "--all" | "-a" => all = true,
+            "--iterations" | "-n" => {
+                let value = value_of(&arg, &mut args)?;
+                iterations = match value.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(ArgsError(format!("\"{}\" is not a positive number of iterations", value))),
+                };
+            },
+            "--inputs" => inputs_dir = PathBuf::from(value_of(&arg, &mut args)?),
+            "--save" => save = Some(PathBuf::from(value_of(&arg, &mut args)?)),
+            "--baseline" => baseline = Some(PathBuf::from(value_of(&arg, &mut args)?)),
+            "--threshold" => {
+                let value = value_of(&arg, &mut args)?;
+                threshold = match value.trim_end_matches('%').parse::<f64>() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => return Err(ArgsError(format!("\"{}\" is not a percentage", value))),
+                };
+            },
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(ArgsError(format!("Unknown option {}", flag)))
+            },
+            input => inputs.push(String::from(input)),
+        }
+    }
+    let days = days_from(day, all)?;
+    if days == Days::All && !inputs.is_empty() {
+        return Err(ArgsError(String::from("--all reads its inputs from --inputs, not from INPUT")))
+    }
+    Ok(BenchOptions { days, inputs, inputs_dir, iterations, save, baseline, threshold })
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<VerifyOptions, ArgsError> {
+    let mut days = Days::All;
+    let mut inputs_dir = PathBuf::from("inputs");
+    let mut record = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => days = Days::One(parse_day(&value_of(&arg, &mut args)?)?),
+            "--all" | "-a" => days = Days::All,
+            "--inputs" => inputs_dir = PathBuf::from(value_of(&arg, &mut args)?),
+            "--record" => record = true,
+            other => return Err(ArgsError(format!("Unknown option {}", other))),
+        }
+    }
+    Ok(VerifyOptions { days, inputs_dir, record })
+}
+
+fn parse_new(mut args: impl Iterator<Item = String>) -> Result<NewOptions, ArgsError> {
+    let mut day: Option<u8> = None;
+    let mut root: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--root" => root = Some(PathBuf::from(value_of(&arg, &mut args)?)),
+            flag if flag.starts_with('-') => return Err(ArgsError(format!("Unknown option {}", flag))),
+            value if day.is_none() => day = Some(parse_day(value)?),
+            extra => return Err(ArgsError(format!("Unexpected argument {}", extra))),
+        }
+    }
+    match day {
+        Some(d) if (1..=25).contains(&d) => Ok(NewOptions { day: d, root }),
+        Some(d) => Err(ArgsError(format!("There's no day {} in Advent of Code", d))),
+        None => Err(ArgsError(String::from("new needs a day"))),
+    }
+}
+
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<GenerateOptions, ArgsError> {
+    let mut day: Option<u8> = None;
+    let mut seed: Option<u64> = None;
+    let mut size: usize = 100;
+    let mut output: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_day(&value_of(&arg, &mut args)?)?),
+            "--seed" => {
+                let value = value_of(&arg, &mut args)?;
+                seed = Some(value.parse().map_err(|_| ArgsError(format!("\"{}\" is not a seed", value)))?);
+            },
+            "--size" | "-n" => {
+                let value = value_of(&arg, &mut args)?;
+                size = value.parse().map_err(|_| ArgsError(format!("\"{}\" is not a size", value)))?;
+            },
+            "--output" | "-o" => output = Some(PathBuf::from(value_of(&arg, &mut args)?)),
+            other => return Err(ArgsError(format!("Unknown option {}", other))),
+        }
+    }
+    let day = day.ok_or_else(|| ArgsError(String::from("generate needs --day")))?;
+    Ok(GenerateOptions { day, seed, size, output })
+}
+
+impl Command {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, ArgsError> {
+        match args.next().as_deref() {
+            Some("run") => Ok(Command::Run(parse_run(args)?)),
+            Some("bench") => Ok(Command::Bench(parse_bench(args)?)),
+            Some("verify") => Ok(Command::Verify(parse_verify(args)?)),
+            Some("new") => Ok(Command::New(parse_new(args)?)),
+            Some("generate") => Ok(Command::Generate(parse_generate(args)?)),
+            Some("list") => Ok(Command::List),
+            Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+            Some(other) => Err(ArgsError(format!("Unknown command {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Command, ArgsError> {
+        Command::parse(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn run_one_part() {
+        let expected = Command::Run(RunOptions {
+            days: Days::One(5),
+            parts: vec![Part::Two],
+            inputs: vec![String::from("input.txt")],
+            inputs_dir: PathBuf::from("inputs"),
+            format: Format::Text,
+        });
+        assert_eq!(Ok(expected), parse("run --day 5 --part 2 input.txt"));
+    }
+
+    #[test]
+    fn run_many_inputs() {
+        let expected = Command::Run(RunOptions {
+            days: Days::One(1),
+            parts: vec![Part::One, Part::Two],
+            inputs: vec![String::from("example*.txt"), String::from("-")],
+            inputs_dir: PathBuf::from("inputs"),
+            format: Format::Text,
+        });
+        assert_eq!(Ok(expected), parse("run --day 1 example*.txt -"));
+    }
+
+    #[test]
+    fn run_all() {
+        let expected = Command::Run(RunOptions {
+            days: Days::All,
+            parts: vec![Part::One, Part::Two],
+            inputs: Vec::new(),
+            inputs_dir: PathBuf::from("my_inputs"),
+            format: Format::Json,
+        });
+        assert_eq!(Ok(expected), parse("run --all --inputs my_inputs --format json"));
+    }
+
+    #[test]
+    fn bench() {
+        let expected = Command::Bench(BenchOptions {
+            days: Days::One(8),
+            inputs: Vec::new(),
+            inputs_dir: PathBuf::from("inputs"),
+            iterations: 50,
+            save: None,
+            baseline: Some(PathBuf::from("base.tsv")),
+            threshold: 5.0,
+        });
+        assert_eq!(Ok(expected), parse("bench --day 8 -n 50 --baseline base.tsv --threshold 5%"));
+        assert!(parse("bench --all -n 0").is_err());
+        assert!(parse("bench --all --threshold fast").is_err());
+        assert!(parse("bench --all input.txt").is_err());
+    }
+
+    #[test]
+    fn verify() {
+        let expected = Command::Verify(VerifyOptions {
+            days: Days::All,
+            inputs_dir: PathBuf::from("inputs"),
+            record: false,
+        });
+        assert_eq!(Ok(expected), parse("verify"));
+        let expected = Command::Verify(VerifyOptions {
+            days: Days::One(3),
+            inputs_dir: PathBuf::from("real"),
+            record: true,
+        });
+        assert_eq!(Ok(expected), parse("verify --day 3 --inputs real --record"));
+        assert!(parse("verify input.txt").is_err());
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(Ok(Command::New(NewOptions { day: 10, root: None })), parse("new 10"));
+        let expected = Command::New(NewOptions { day: 12, root: Some(PathBuf::from("rust")) });
+        assert_eq!(Ok(expected), parse("new --root rust 12"));
+        assert!(parse("new").is_err());
+        assert!(parse("new 26").is_err());
+        assert!(parse("new 10 11").is_err());
+    }
+
+    #[test]
+    fn generate() {
+        let expected = Command::Generate(GenerateOptions { day: 8, seed: Some(42), size: 100, output: None });
+        assert_eq!(Ok(expected), parse("generate --day 8 --seed 42"));
+        let expected = Command::Generate(GenerateOptions {
+            day: 3,
+            seed: None,
+            size: 10,
+            output: Some(PathBuf::from("big.txt")),
+        });
+        assert_eq!(Ok(expected), parse("generate -d 3 -n 10 -o big.txt"));
+        assert!(parse("generate --seed 1").is_err());
+        assert!(parse("generate --day 1 --seed -1").is_err());
+    }
+
+    #[test]
+    fn invalid_runs() {
+        assert!(parse("run").is_err());
+        assert!(parse("run --day 1 --all").is_err());
+        assert!(parse("run --day x").is_err());
+        assert!(parse("run --day 1 --part 3").is_err());
+        assert!(parse("run --all input.txt").is_err());
+        assert!(parse("run --day 1 --frobnicate").is_err());
+        assert!(parse("run --day 1 --format yaml").is_err());
+        assert!(parse("frobnicate").is_err());
+    }
+}
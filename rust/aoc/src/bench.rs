@@ -0,0 +1,178 @@
+use std::{ collections::HashMap, fmt::Display, hint::black_box, time::{ Duration, Instant } };
+
+use aoc_common::{ parse, ParseError, Part, Solution };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Parse,
+    Solve(Part),
+}
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::Solve(Part::One), Step::Solve(Part::Two)];
+}
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+fn parse_step(line: &str, s: &str) -> Result<Step, ParseError> {
+    match s {
+        "parse" => Ok(Step::Parse),
+        "part1" => Ok(Step::Solve(Part::One)),
+        "part2" => Ok(Step::Solve(Part::Two)),
+        _ => Err(parse::expected(line, s, "parse, part1 or part2")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() {
+            0 => return None,
+            n if n % 2 == 0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+        Some(Stats { min: sorted[0], median, max: sorted[sorted.len() - 1] })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub step: Step,
+    pub input: String,
+    pub bytes: usize,
+    pub stats: Stats,
+}
+impl Measurement {
+    // Megabytes of input per second, going by the median.
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.stats.median.as_secs_f64();
+        if seconds == 0.0 {
+            return f64::INFINITY;
+        }
+        self.bytes as f64 / seconds / 1_000_000.0
+    }
+    fn key(&self) -> (u8, Step, String) {
+        (self.day, self.step, self.input.clone())
+    }
+}
+
+// Runs one step `iterations` times, stopping at the first error.
+pub fn time_step(solution: &dyn Solution, step: Step, input: &[&str], iterations: usize) -> Result<Stats, String> {
+    let mut samples: Vec<Duration> = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let result = match step {
+            Step::Parse => solution.parse(black_box(input)).map(|_| String::new()),
+            Step::Solve(part) => solution.solve(part, black_box(input)),
+        };
+        samples.push(start.elapsed());
+        black_box(result).map_err(|e| e.to_string())?;
+    }
+    Stats::from_samples(&samples[..]).ok_or_else(|| String::from("no iterations were run"))
+}
+
+pub type Baseline = HashMap<(u8, Step, String), Duration>;
+
+// One "day<TAB>step<TAB>median_ns<TAB>input" line per measurement. The input goes last
+// so that its name can contain anything but a newline.
+pub fn render_baseline(measurements: &[Measurement]) -> String {
+    let mut lines: Vec<String> = vec![String::from("# day\tstep\tmedian_ns\tinput")];
+    lines.extend(measurements.iter().map(|m| {
+        format!("{}\t{}\t{}\t{}", m.day, m.step, m.stats.median.as_nanos(), m.input)
+    }));
+    lines.join("\n") + "\n"
+}
+
+pub fn parse_baseline(lines: &[&str]) -> Result<Baseline, ParseError> {
+    let mut baseline = Baseline::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parse_line = || -> Result<((u8, Step, String), Duration), ParseError> {
+            let (day, rest) = parse::split_once(line, line, '\t')?;
+            let (step, rest) = parse::split_once(line, rest, '\t')?;
+            let (nanos, input) = parse::split_once(line, rest, '\t')?;
+            let day: u8 = parse::parse_number(line, day)?;
+            let nanos: u64 = parse::parse_number(line, nanos)?;
+            Ok(((day, parse_step(line, step)?, String::from(input)), Duration::from_nanos(nanos)))
+        };
+        let (key, median) = parse_line().map_err(|e| e.on_line(i + 1))?;
+        baseline.insert(key, median);
+    }
+    Ok(baseline)
+}
+
+// How much slower (positive) or faster (negative) the median is than the baseline, as a percentage.
+pub fn change_from(baseline: &Baseline, measurement: &Measurement) -> Option<f64> {
+    let before = baseline.get(&measurement.key())?.as_secs_f64();
+    if before == 0.0 {
+        return None;
+    }
+    Some((measurement.stats.median.as_secs_f64() - before) / before * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(step: Step, median_ms: u64) -> Measurement {
+        let median = Duration::from_millis(median_ms);
+        Measurement {
+            day: 5,
+            step,
+            input: String::from("inputs/day5.txt"),
+            bytes: 2_000_000,
+            stats: Stats { min: median, median, max: median },
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(&[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(Stats { min: ms(1), median: ms(3), max: ms(5) }, stats);
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!(ms(3), stats.median);
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn throughput() {
+        assert_eq!(2.0, measurement(Step::Parse, 1000).throughput());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let measurements = [measurement(Step::Parse, 2), measurement(Step::Solve(Part::Two), 40)];
+        let rendered = render_baseline(&measurements[..]);
+        let lines: Vec<&str> = rendered.lines().collect();
+        let baseline = parse_baseline(&lines[..]).unwrap();
+        assert_eq!(2, baseline.len());
+        assert_eq!(Some(0.0), change_from(&baseline, &measurements[1]));
+        assert_eq!(Some(50.0), change_from(&baseline, &measurement(Step::Solve(Part::Two), 60)).map(|c| c.round()));
+        assert_eq!(None, change_from(&baseline, &measurement(Step::Solve(Part::One), 60)));
+    }
+
+    #[test]
+    fn invalid_baseline() {
+        let error = parse_baseline(&["# header", "5\tpart3\t100\tinput.txt"]).unwrap_err();
+        assert_eq!(2, error.line);
+        assert!(parse_baseline(&["5\tparse\tslow\tinput.txt"]).is_err());
+        assert!(parse_baseline(&["5\tparse"]).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::{ path::Path, time::{ Duration, SystemTime, UNIX_EPOCH } };
+
+use aoc_common::{ cli, input, report, Format, Part, Record, Rng, Solution, Source };
+
+use answers::{ Answers, Verdict };
+use args::{ BenchOptions, Command, Days, GenerateOptions, NewOptions, RunOptions, VerifyOptions };
+use bench::{ Baseline, Measurement, Step };
+use table::Table;
+
+mod answers;
+mod args;
+mod bench;
+mod registry;
+mod scaffold;
+mod table;
+
+fn solutions_for(days: &Days) -> Option<Vec<Box<dyn Solution>>> {
+    match days {
+        Days::One(day) => match registry::get(*day) {
+            Some(s) => Some(vec![s]),
+            None => {
+                eprintln!("Day {} hasn't been solved yet.", day);
+                None
+            },
+        },
+        Days::All => Some(registry::all()),
+    }
+}
+
+// Returns whether every part that was run succeeded.
+fn run(options: RunOptions) -> bool {
+    let Some(solutions) = solutions_for(&options.days) else {
+        return false
+    };
+
+    let mut records: Vec<Record> = Vec::new();
+    for solution in solutions {
+        let day = solution.day();
+        let sources = match options.days {
+            Days::One(_) => match input::expand_sources(&options.inputs[..]) {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    return false
+                },
+            },
+            Days::All => vec![Source::File(options.inputs_dir.join(format!("day{}.txt", day)))],
+        };
+        for source in sources {
+            records.extend(cli::solve_source(solution.as_ref(), &source, &options.parts[..]));
+        }
+    }
+
+    match options.format {
+        Format::Text => println!("{}", render_table(&records[..])),
+        Format::Json => println!("{}", report::render_json(&records[..])),
+        Format::Csv => println!("{}", report::render_csv(&records[..])),
+    }
+    records.iter().all(|r| r.error.is_none())
+}
+
+fn render_table(records: &[Record]) -> String {
+    let mut table = Table::new(&["Day", "Part", "Input", "Answer", "Time (ms)"]);
+    for record in records {
+        let answer = match (&record.answer, &record.error) {
+            (Some(a), _) => a.clone(),
+            (None, Some(e)) => format!("error: {}", e),
+            (None, None) => String::new(),
+        };
+        table.push(vec![
+            record.day.to_string(),
+            record.part.to_string(),
+            record.input.clone(),
+            answer,
+            record.elapsed_ms(),
+        ]);
+    }
+    table.render()
+}
+
+// Returns whether every step could be timed without regressing past the threshold.
+fn bench(options: BenchOptions) -> bool {
+    let Some(solutions) = solutions_for(&options.days) else {
+        return false
+    };
+    let baseline: Option<Baseline> = match &options.baseline {
+        None => None,
+        Some(path) => {
+            let source = Source::File(path.clone());
+            let lines = match source.read_lines() {
+                Ok(l) => l,
+                Err(e) => {
+                    eprintln!("error: {}: {}", source, e);
+                    return false
+                },
+            };
+            let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+            match bench::parse_baseline(&lines_str[..]) {
+                Ok(b) => Some(b),
+                Err(e) => {
+                    eprint!("{}", e.diagnostic(&source.to_string(), &lines_str[..]));
+                    return false
+                },
+            }
+        },
+    };
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    let mut success = true;
+    for solution in solutions {
+        let day = solution.day();
+        let sources = if options.inputs.is_empty() {
+            vec![Source::File(options.inputs_dir.join(format!("day{}.txt", day)))]
+        }
+        else {
+            match input::expand_sources(&options.inputs[..]) {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    return false
+                },
+            }
+        };
+        for source in sources {
+            let lines = match source.read_lines() {
+                Ok(l) => l,
+                Err(e) => {
+                    eprintln!("error: {}: {}", source, e);
+                    success = false;
+                    continue;
+                },
+            };
+            let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+            let bytes: usize = lines.iter().map(|l| l.len() + 1).sum();
+            for step in Step::ALL {
+                match bench::time_step(solution.as_ref(), step, &lines_str[..], options.iterations) {
+                    Ok(stats) => measurements.push(Measurement { day, step, input: source.to_string(), bytes, stats }),
+                    Err(e) => {
+                        eprintln!("error: day {} {} on {}: {}", day, step, source, e);
+                        success = false;
+                    },
+                }
+            }
+        }
+    }
+
+    let ms = |d: Duration| format!("{:.3}", d.as_secs_f64() * 1000.0);
+    let mut header = vec!["Day", "Step", "Input", "Min (ms)", "Median (ms)", "Max (ms)", "MB/s"];
+    if baseline.is_some() {
+        header.push("vs baseline");
+    }
+    let mut table = Table::new(&header[..]);
+    for measurement in &measurements {
+        let mut row = vec![
+            measurement.day.to_string(),
+            measurement.step.to_string(),
+            measurement.input.clone(),
+            ms(measurement.stats.min),
+            ms(measurement.stats.median),
+            ms(measurement.stats.max),
+            format!("{:.2}", measurement.throughput()),
+        ];
+        if let Some(baseline) = &baseline {
+            row.push(match bench::change_from(baseline, measurement) {
+                None => String::from("new"),
+                Some(change) if change > options.threshold => {
+                    success = false;
+                    format!("{:+.1}% REGRESSION", change)
+                },
+                Some(change) => format!("{:+.1}%", change),
+            });
+        }
+        table.push(row);
+    }
+    println!("{}", table.render());
+
+    if let Some(path) = &options.save {
+        if let Err(e) = std::fs::write(path, bench::render_baseline(&measurements[..])) {
+            eprintln!("error: couldn't save the baseline to {}: {}", path.display(), e);
+            return false
+        }
+    }
+    success
+}
+
+// Returns whether nothing failed. Answers that aren't known yet don't count as failures.
+fn verify(options: VerifyOptions) -> bool {
+    let Some(solutions) = solutions_for(&options.days) else {
+        return false
+    };
+    let mut table = Table::new(&["Day", "Part", "Status", "Answer", "Expected"]);
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solution in solutions {
+        let day = solution.day();
+        let input_path = options.inputs_dir.join(format!("day{}.txt", day));
+        if !input_path.exists() {
+            for part in Part::ALL {
+                table.push(vec![day.to_string(), part.to_string(), String::from("missing input"), String::new(), String::new()]);
+                missing += 1;
+            }
+            continue;
+        }
+
+        let answers_path = answers::path_for(&input_path);
+        let mut known = match load_answers(&answers_path) {
+            Some(a) => a,
+            None => {
+                for part in Part::ALL {
+                    table.push(vec![day.to_string(), part.to_string(), String::from("error"), String::new(), String::new()]);
+                    failed += 1;
+                }
+                continue;
+            },
+        };
+        let mut recorded = false;
+        for record in cli::solve_source(solution.as_ref(), &Source::File(input_path), &Part::ALL) {
+            let expected = known.get(record.part).cloned().unwrap_or_default();
+            let (status, answer) = match (&record.answer, &record.error) {
+                (Some(answer), _) => match answers::verdict(known.get(record.part), answer) {
+                    Verdict::Pass => {
+                        passed += 1;
+                        ("pass", answer.clone())
+                    },
+                    Verdict::Fail { .. } => {
+                        failed += 1;
+                        ("FAIL", answer.clone())
+                    },
+                    Verdict::Missing if options.record => {
+                        known.set(record.part, answer.clone());
+                        recorded = true;
+                        ("recorded", answer.clone())
+                    },
+                    Verdict::Missing => {
+                        missing += 1;
+                        ("missing", answer.clone())
+                    },
+                },
+                (None, e) => {
+                    failed += 1;
+                    ("error", e.clone().unwrap_or_default())
+                },
+            };
+            table.push(vec![day.to_string(), record.part.to_string(), String::from(status), answer, expected]);
+        }
+        if recorded {
+            if let Err(e) = std::fs::write(&answers_path, known.render()) {
+                eprintln!("error: couldn't save the answers to {}: {}", answers_path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    println!("{}", table.render());
+    println!("\n{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+// A missing answers file just means nothing is known yet. None means the file couldn't be used.
+fn load_answers(path: &Path) -> Option<Answers> {
+    if !path.exists() {
+        return Some(Answers::default());
+    }
+    let lines = match std::fs::read_to_string(path) {
+        Ok(text) => text.lines().map(String::from).collect::<Vec<String>>(),
+        Err(e) => {
+            eprintln!("error: {}: {}", path.display(), e);
+            return None
+        },
+    };
+    let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+    match answers::parse_answers(&lines_str[..]) {
+        Ok(a) => Some(a),
+        Err(e) => {
+            eprint!("{}", e.diagnostic(&path.display().to_string(), &lines_str[..]));
+            None
+        },
+    }
+}
+
+fn new_day(options: NewOptions) -> bool {
+    let root = match options.root {
+        Some(r) => r,
+        None => match std::env::current_dir().ok().and_then(|dir| scaffold::find_workspace(&dir)) {
+            Some(r) => r,
+            None => {
+                eprintln!("error: couldn't find the workspace, try --root");
+                return false
+            },
+        },
+    };
+    match scaffold::new_day(&root, options.day) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            true
+        },
+        Err(e) => {
+            eprintln!("error: {}", e);
+            false
+        },
+    }
+}
+
+fn generate(options: GenerateOptions) -> bool {
+    let Some(solution) = registry::get(options.day) else {
+        eprintln!("Day {} hasn't been solved yet.", options.day);
+        return false
+    };
+    let seed = options.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let lines = solution.generate(&mut Rng::new(seed), options.size);
+    let text = lines.join("\n") + "\n";
+    match &options.output {
+        None => print!("{}", text),
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, text) {
+                eprintln!("error: couldn't write {}: {}", path.display(), e);
+                return false
+            }
+        },
+    }
+    true
+}
+
+fn main() {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, args::USAGE);
+            std::process::exit(2);
+        },
+    };
+    match command {
+        Command::Run(options) => {
+            if !run(options) {
+                std::process::exit(1);
+            }
+        },
+        Command::Bench(options) => {
+            if !bench(options) {
+                std::process::exit(1);
+            }
+        },
+        Command::Verify(options) => {
+            if !verify(options) {
+                std::process::exit(1);
+            }
+        },
+        Command::New(options) => {
+            if !new_day(options) {
+                std::process::exit(1);
+            }
+        },
+        Command::Generate(options) => {
+            if !generate(options) {
+                std::process::exit(1);
+            }
+        },
+        Command::List => {
+            for solution in registry::all() {
+                println!("day{}", solution.day());
+            }
+        },
+        Command::Help => println!("{}", args::USAGE),
+    }
+}
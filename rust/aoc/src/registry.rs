@@ -0,0 +1,39 @@
+use aoc_common::Solution;
+
+pub fn all() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(day1::Day1),
+        Box::new(day2::Day2),
+        Box::new(day3::Day3),
+        Box::new(day4::Day4),
+        Box::new(day5::Day5),
+        Box::new(day6::Day6),
+        Box::new(day7::Day7),
+        Box::new(day8::Day8),
+        Box::new(day9::Day9),
+    ]
+}
+
+pub fn get(day: u8) -> Option<Box<dyn Solution>> {
+    all().into_iter().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_unique_and_ordered() {
+        let days: Vec<u8> = all().iter().map(|s| s.day()).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, days);
+    }
+
+    #[test]
+    fn get_day() {
+        assert_eq!(Some(5), get(5).map(|s| s.day()));
+        assert!(get(26).is_none());
+    }
+}
@@ -0,0 +1,331 @@
+use std::{ fmt::Display, path::{ Path, PathBuf } };
+
+const CARGO_TOML: &str = r#"[package]
+name = "day__DAY__"
+version.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc-common = { path = "../aoc-common" }
+"#;
+
+const MAIN_RS: &str = r#"fn main() {
+    aoc_common::cli::run_day(&day__DAY__::Day__DAY__);
+}
+"#;
+
+const LIB_RS: &str = r#"use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::parse_input;
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    part1::solve(input)
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    part2::solve(input)
+}
+
+pub struct Day__DAY__;
+impl Solution for Day__DAY__ {
+    fn day(&self) -> u8 {
+        __DAY__
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        parse_input(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
+"#;
+
+const GENERATE_RS: &str = r#"use aoc_common::Rng;
+
+// TODO: make up inputs that look like the real puzzle input.
+pub fn generate(rng: &mut Rng, lines: usize) -> Vec<String> {
+    (0..lines.max(1)).map(|_| rng.range(0..1000).to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..20 {
+            let lines = generate(&mut Rng::new(seed), 1 + seed as usize);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            solve_part1(&lines_str[..]).unwrap();
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+}
+"#;
+
+const PART1_RS: &str = r#"use aoc_common::{ parse, ParseError };
+
+// TODO: parse the real puzzle input, this just reads a number per line.
+pub fn parse_input(lines: &[&str]) -> Result<Vec<usize>, ParseError> {
+    parse::parse_lines(lines, |line| parse::parse_number(line, line.trim()))
+}
+
+pub fn solve(lines: &[&str]) -> Result<usize, ParseError> {
+    Ok(parse_input(lines)?.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = String::from(
+"1
+2
+3"
+        );
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(6, solve(&lines[..]).unwrap());
+    }
+}
+"#;
+
+const PART2_RS: &str = r#"use aoc_common::ParseError;
+
+use crate::part1::parse_input;
+
+pub fn solve(lines: &[&str]) -> Result<usize, ParseError> {
+    Ok(parse_input(lines)?.into_iter().max().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = String::from(
+"1
+2
+3"
+        );
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(3, solve(&lines[..]).unwrap());
+    }
+}
+"#;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaffoldError(pub String);
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::error::Error for ScaffoldError {}
+
+fn fill(template: &str, day: u8) -> String {
+    template.replace("__DAY__", &day.to_string())
+}
+
+// The day number written straight after `marker` in a line, if there is one.
+fn day_after(line: &str, marker: &str) -> Option<u8> {
+    let rest = &line[(line.find(marker)? + marker.len())..];
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+// Adds `new_line` just after the last line for an earlier day, keeping the days in order.
+fn insert_in_order(text: &str, marker: &str, day: u8, new_line: &str, file: &str) -> Result<String, ScaffoldError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let days: Vec<Option<u8>> = lines.iter().map(|l| day_after(l, marker)).collect();
+    if days.contains(&Some(day)) {
+        return Err(ScaffoldError(format!("day {} is already in {}", day, file)));
+    }
+    let position = match days.iter().rposition(|d| d.is_some_and(|d| d < day)) {
+        Some(i) => i + 1,
+        None => days
+            .iter()
+            .position(|d| d.is_some())
+            .ok_or_else(|| ScaffoldError(format!("couldn't find where to add day {} to {}", day, file)))?,
+    };
+    let mut to_return: Vec<&str> = lines[..position].to_vec();
+    to_return.push(new_line);
+    to_return.extend(&lines[position..]);
+    Ok(to_return.join("\n") + "\n")
+}
+
+pub fn add_workspace_member(text: &str, day: u8) -> Result<String, ScaffoldError> {
+    insert_in_order(text, "\"day", day, &format!("    \"day{}\",", day), "Cargo.toml")
+}
+
+pub fn add_runner_dependency(text: &str, day: u8) -> Result<String, ScaffoldError> {
+    let new_line = format!("day{0} = {{ path = \"../day{0}\" }}", day);
+    insert_in_order(text, "path = \"../day", day, &new_line, "aoc/Cargo.toml")
+}
+
+pub fn add_to_registry(text: &str, day: u8) -> Result<String, ScaffoldError> {
+    let new_line = format!("        Box::new(day{0}::Day{0}),", day);
+    insert_in_order(text, "Box::new(day", day, &new_line, "aoc/src/registry.rs")
+}
+
+// The directory holding the workspace's Cargo.toml, looking up from `start`.
+pub fn find_workspace(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        manifest.lines().any(|l| l.trim() == "[workspace]").then(|| dir.to_path_buf())
+    })
+}
+
+// Writes each file in turn. If one can't be written, the ones before it are put back the way
+// they were: files in `originals` get their old contents back, and any others are removed.
+fn write_or_undo(files: &[(PathBuf, String)], originals: &[(PathBuf, String)]) -> Result<(), ScaffoldError> {
+    for (i, (path, contents)) in files.iter().enumerate() {
+        if let Err(e) = std::fs::write(path, contents) {
+            // Undoing is best effort, since the error that matters is the one that stopped us.
+            for (written, _) in &files[..i] {
+                let _ = match originals.iter().find(|(p, _)| p == written) {
+                    Some((_, original)) => std::fs::write(written, original),
+                    None => std::fs::remove_file(written),
+                };
+            }
+            return Err(ScaffoldError(format!("{}: {}", path.display(), e)));
+        }
+    }
+    Ok(())
+}
+
+// Creates day<N> in the workspace at `root` and registers it everywhere, returning the files written.
+// Every edit is worked out before anything is written, and if a write fails then what was already
+// written is undone, so unless undoing fails too, a failure leaves the workspace alone.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let io_error = |path: &Path, e: std::io::Error| ScaffoldError(format!("{}: {}", path.display(), e));
+    let read = |path: &Path| std::fs::read_to_string(path).map_err(|e| io_error(path, e));
+
+    let crate_dir = root.join(format!("day{}", day));
+    if crate_dir.exists() {
+        return Err(ScaffoldError(format!("{} already exists", crate_dir.display())));
+    }
+    let workspace_manifest = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("registry.rs");
+    let originals: Vec<(PathBuf, String)> = vec![
+        (workspace_manifest.clone(), read(&workspace_manifest)?),
+        (runner_manifest.clone(), read(&runner_manifest)?),
+        (registry.clone(), read(&registry)?),
+    ];
+    let edits: Vec<(PathBuf, String)> = vec![
+        (workspace_manifest, add_workspace_member(&originals[0].1, day)?),
+        (runner_manifest, add_runner_dependency(&originals[1].1, day)?),
+        (registry, add_to_registry(&originals[2].1, day)?),
+    ];
+    let new_files: Vec<(PathBuf, String)> = vec![
+        (crate_dir.join("Cargo.toml"), fill(CARGO_TOML, day)),
+        (crate_dir.join("src").join("main.rs"), fill(MAIN_RS, day)),
+        (crate_dir.join("src").join("lib.rs"), fill(LIB_RS, day)),
+        (crate_dir.join("src").join("generate.rs"), fill(GENERATE_RS, day)),
+        (crate_dir.join("src").join("part1.rs"), fill(PART1_RS, day)),
+        (crate_dir.join("src").join("part2.rs"), fill(PART2_RS, day)),
+    ];
+
+    let src_dir = crate_dir.join("src");
+    std::fs::create_dir_all(&src_dir).map_err(|e| io_error(&src_dir, e))?;
+    let files: Vec<(PathBuf, String)> = new_files.into_iter().chain(edits).collect();
+    if let Err(e) = write_or_undo(&files[..], &originals[..]) {
+        let _ = std::fs::remove_dir_all(&crate_dir);
+        return Err(e);
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMBERS: &str = "[workspace]
+members = [
+    \"aoc\",
+    \"day1\",
+    \"day9\",
+    \"day11\",
+]
+";
+
+    #[test]
+    fn members_stay_in_order() {
+        let expected = "[workspace]
+members = [
+    \"aoc\",
+    \"day1\",
+    \"day9\",
+    \"day10\",
+    \"day11\",
+]
+";
+        assert_eq!(Ok(String::from(expected)), add_workspace_member(MEMBERS, 10));
+        assert!(add_workspace_member(MEMBERS, 9).is_err());
+        assert!(add_workspace_member("[workspace]\n", 10).is_err());
+    }
+
+    #[test]
+    fn first_day_goes_first() {
+        let text = "vec![\n        Box::new(day2::Day2),\n    ]\n";
+        let expected = "vec![\n        Box::new(day1::Day1),\n        Box::new(day2::Day2),\n    ]\n";
+        assert_eq!(Ok(String::from(expected)), add_to_registry(text, 1));
+    }
+
+    #[test]
+    fn dependencies() {
+        let text = "[dependencies]\naoc-common = { path = \"../aoc-common\" }\nday9 = { path = \"../day9\" }\n";
+        let added = add_runner_dependency(text, 10).unwrap();
+        assert!(added.ends_with("day9 = { path = \"../day9\" }\nday10 = { path = \"../day10\" }\n"));
+    }
+
+    #[test]
+    fn new_day_in_workspace() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), MEMBERS).unwrap();
+        std::fs::write(root.join("aoc").join("Cargo.toml"), "day9 = { path = \"../day9\" }\n").unwrap();
+        std::fs::write(root.join("aoc").join("src").join("registry.rs"), "        Box::new(day9::Day9),\n").unwrap();
+
+        assert_eq!(Some(root.clone()), find_workspace(&root.join("aoc").join("src")));
+        assert_eq!(9, new_day(&root, 10).unwrap().len());
+        let lib = std::fs::read_to_string(root.join("day10").join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day10;"));
+        assert!(!lib.contains("__DAY__"));
+        assert!(new_day(&root, 10).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn undoes_failed_writes() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-undo-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let (existing, new) = (root.join("existing.txt"), root.join("new.txt"));
+        std::fs::write(&existing, "before").unwrap();
+        let files = [
+            (existing.clone(), String::from("after")),
+            (new.clone(), String::from("new")),
+            (root.join("missing").join("file.txt"), String::from("can't be written")),
+        ];
+        let error = write_or_undo(&files, &[(existing.clone(), String::from("before"))]).unwrap_err();
+        assert!(error.0.contains("missing"));
+        assert_eq!("before", std::fs::read_to_string(&existing).unwrap());
+        assert!(!new.exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+// Plain text table, with every column padded to its widest cell.
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+impl Table {
+    pub fn new(header: &[&str]) -> Table {
+        Table {
+            header: header.iter().map(|s| String::from(*s)).collect(),
+            rows: Vec::new(),
+        }
+    }
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.header.iter().map(|s| s.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+        widths
+    }
+    pub fn render(&self) -> String {
+        let widths = self.widths();
+        let format_row = |row: &[String]| -> String {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        let mut lines: Vec<String> = vec![format_row(&self.header[..])];
+        lines.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("  "));
+        for row in &self.rows {
+            lines.push(format_row(&row[..]));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let mut table = Table::new(&["Day", "Part", "Answer"]);
+        table.push(vec![String::from("1"), String::from("1"), String::from("54388")]);
+        table.push(vec![String::from("10"), String::from("2"), String::from("7")]);
+        let expected = "Day  Part  Answer
+---  ----  ------
+1    1     54388
+10   2     7";
+        assert_eq!(expected, table.render());
+    }
+}
@@ -0,0 +1,220 @@
+use aoc_common::{ input, Source };
+
+use crate::{
+    explain::{ self, Colour },
+    stream::{ self, ErrorPolicy, StreamError, Summary },
+    vocabulary::{ Decoder, DigitVocabulary },
+};
+
+pub const USAGE: &str = "Usage: day1 --explain [OPTIONS] [INPUT...]
+       day1 --stream [OPTIONS] [INPUT...]
+
+--explain shows, for every line, which tokens were found, which were picked as the first and last, and the value they make.
+--stream adds up the values a line at a time without holding the input in memory, and prints the total for each input.
+
+Options:
+  --part <1|2>                   Part 1 only looks at ASCII digits. Part 2 (the default) uses the English words too.
+  --vocabulary <FILE>            The digit words to use for part 2.
+  --colour <auto|always|never>   Whether --explain highlights the tokens it picked.
+  --on-error <POLICY>            What --stream does with a line that doesn't decode: strict (the default) stops,
+                                 skip leaves it out, and default=<N> counts it as N. Skipped lines are listed on stderr.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Explain,
+    Stream,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub part2: bool,
+    pub colour: Colour,
+    pub vocabulary: Option<String>,
+    pub on_error: ErrorPolicy,
+    pub inputs: Vec<String>,
+}
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut mode = None;
+        let mut options = Options {
+            mode: Mode::Explain,
+            part2: true,
+            colour: Colour::Auto,
+            vocabulary: None,
+            on_error: ErrorPolicy::Strict,
+            inputs: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+            match arg.as_str() {
+                "--explain" | "--stream" => {
+                    let new = if arg == "--explain" { Mode::Explain } else { Mode::Stream };
+                    if mode.is_some_and(|m| m != new) {
+                        return Err(String::from("--explain and --stream can't be used together"));
+                    }
+                    mode = Some(new);
+                },
+                "--part" | "-p" => options.part2 = match value()?.as_str() {
+                    "1" => false,
+                    "2" => true,
+                    other => return Err(format!("\"{}\" is not a part, expected 1 or 2", other)),
+                },
+                "--colour" | "--color" => options.colour = match value()?.as_str() {
+                    "auto" => Colour::Auto,
+                    "always" => Colour::Always,
+                    "never" => Colour::Never,
+                    other => return Err(format!("\"{}\" is not auto, always or never", other)),
+                },
+                "--vocabulary" => options.vocabulary = Some(value()?),
+                "--on-error" => options.on_error = value()?.parse()?,
+                flag if flag.starts_with('-') && flag != "-" => return Err(format!("Unknown option {}", flag)),
+                path => options.inputs.push(String::from(path)),
+            }
+        }
+        let Some(mode) = mode else {
+            return Err(String::from("Expected --explain or --stream"))
+        };
+        if !options.part2 && options.vocabulary.is_some() {
+            return Err(String::from("--vocabulary only applies to part 2"));
+        }
+        if mode == Mode::Explain && options.on_error != ErrorPolicy::Strict {
+            return Err(String::from("--on-error only applies to --stream"));
+        }
+        options.mode = mode;
+        Ok(options)
+    }
+
+    pub fn decoder(&self) -> Result<Decoder, String> {
+        let Some(path) = &self.vocabulary else {
+            let vocabulary = if self.part2 { DigitVocabulary::english() } else { DigitVocabulary::new().ascii_digits() };
+            return Ok(vocabulary.decoder());
+        };
+        let source = Source::File(path.into());
+        let lines = source.read_lines().map_err(|e| format!("error: {}: {}", source, e))?;
+        let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+        DigitVocabulary::from_lines(&lines_str[..])
+            .map(|v| v.decoder())
+            .map_err(|e| e.diagnostic(path, &lines_str[..]))
+    }
+}
+
+// Everything the policy let through, so nothing is dropped silently.
+pub fn render_offending(summary: &Summary, policy: ErrorPolicy) -> String {
+    let action = match policy {
+        ErrorPolicy::Default(value) => format!("counted as {}", value),
+        _ => String::from("skipped"),
+    };
+    let mut to_return = format!(
+        "{} of {} lines {} because they didn't decode:\n",
+        summary.offending.len(),
+        summary.lines,
+        action,
+    );
+    let width = summary.offending.last().map_or(1, |o| o.error.line.to_string().len());
+    for offending in &summary.offending {
+        to_return.push_str(&format!(
+            "  {:>width$} | {}  ({})\n",
+            offending.error.line,
+            offending.text,
+            offending.error.kind,
+            width = width,
+        ));
+    }
+    to_return
+}
+
+fn print_totals(decoder: &Decoder, sources: &[Source], policy: ErrorPolicy) -> bool {
+    let mut success = true;
+    for source in sources {
+        let name = source.to_string();
+        let summary = source
+            .open()
+            .map_err(|e| format!("error: {}\n", e))
+            .and_then(|reader| {
+                stream::values(reader, |l| decoder.decode(l))
+                    .on_error(policy)
+                    .summarise()
+                    .map_err(|e| match e {
+                        StreamError::Parse(o) => o.diagnostic(&name),
+                        e => format!("error: {}: {}\n", name, e),
+                    })
+            });
+        match summary {
+            Ok(summary) => {
+                if !summary.offending.is_empty() {
+                    eprint!("{}: {}", name, render_offending(&summary, policy));
+                }
+                match sources.len() {
+                    1 => println!("{}", summary.total),
+                    _ => println!("{}: {}", name, summary.total),
+                }
+            },
+            Err(e) => {
+                eprint!("{}", e);
+                success = false;
+            },
+        }
+    }
+    success
+}
+
+// Returns whether everything decoded.
+pub fn run(options: &Options) -> bool {
+    let decoder = match options.decoder() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            return false
+        },
+    };
+    let sources = match input::expand_sources(&options.inputs[..]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false
+        },
+    };
+    match options.mode {
+        Mode::Explain => explain::print(&decoder, &sources[..], options.colour.enabled()),
+        Mode::Stream => print_totals(&decoder, &sources[..], options.on_error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options() {
+        let parse = |s: &str| Options::parse(s.split_whitespace().map(String::from));
+        let expected = Options {
+            mode: Mode::Explain,
+            part2: false,
+            colour: Colour::Never,
+            vocabulary: None,
+            on_error: ErrorPolicy::Strict,
+            inputs: vec![String::from("input.txt")],
+        };
+        assert_eq!(Ok(expected), parse("--explain --part 1 --colour never input.txt"));
+        assert_eq!(Mode::Stream, parse("--stream -").unwrap().mode);
+        assert_eq!(ErrorPolicy::Default(0), parse("--stream --on-error default=0").unwrap().on_error);
+        assert!(parse("--explain --on-error skip").is_err());
+        assert!(parse("--part 1 input.txt").is_err());
+        assert!(parse("--explain --stream").is_err());
+        assert!(parse("--explain --part 1 --vocabulary french.toml").is_err());
+        assert!(parse("--explain --colour sometimes").is_err());
+        assert!(parse("--stream --format json").is_err());
+    }
+
+    #[test]
+    fn offending_lines() {
+        let document = ["1abc2", "abc", "treb7uchet"];
+        let summary = stream::Values::new(document, crate::part1::decode_calibration_value)
+            .on_error(ErrorPolicy::Skip)
+            .summarise()
+            .unwrap();
+        let expected = "1 of 3 lines skipped because they didn't decode:\n  2 | abc  (missing a digit)\n";
+        assert_eq!(expected, render_offending(&summary, ErrorPolicy::Skip));
+    }
+}
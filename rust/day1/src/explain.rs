@@ -0,0 +1,174 @@
+use std::io::IsTerminal;
+
+use aoc_common::{ ParseError, Source };
+
+use crate::{ scanner::Match, vocabulary::Decoder };
+
+const FIRST: &str = "\x1b[1;32m";
+const LAST: &str = "\x1b[1;34m";
+const BOTH: &str = "\x1b[1;33m";
+const OTHER: &str = "\x1b[4m";
+const RESET: &str = "\x1b[0m";
+
+// Why a line decoded to what it did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation<'a> {
+    pub line: &'a str,
+    // Every token the decoder considered, in the order they start.
+    pub matches: Vec<Match>,
+    pub first: Option<Match>,
+    pub last: Option<Match>,
+    pub value: Result<u32, ParseError>,
+}
+impl Explanation<'_> {
+    fn token(&self, m: &Match) -> String {
+        format!("\"{}\" at {}..{}", &self.line[m.start..m.end], m.start, m.end)
+    }
+    pub fn summary(&self) -> String {
+        match (&self.value, self.first, self.last) {
+            (Ok(value), Some(first), Some(last)) => {
+                format!("{} (first {}, last {})", value, self.token(&first), self.token(&last))
+            },
+            (Err(e), _, _) => format!("error: {}", e.kind),
+            (Ok(value), _, _) => value.to_string(),
+        }
+    }
+    // The line with the first token in green, the last in blue (yellow where they overlap),
+    // and any other tokens underlined.
+    pub fn highlighted(&self) -> String {
+        let within = |m: Option<Match>, i: usize| m.is_some_and(|m| (m.start..m.end).contains(&i));
+        let style = |i: usize| -> &str {
+            match (within(self.first, i), within(self.last, i)) {
+                (true, true) => BOTH,
+                (true, false) => FIRST,
+                (false, true) => LAST,
+                _ if self.matches.iter().any(|m| within(Some(*m), i)) => OTHER,
+                _ => "",
+            }
+        };
+        let mut to_return = String::new();
+        let mut current = "";
+        for (i, c) in self.line.char_indices() {
+            let next = style(i);
+            if next != current {
+                if !current.is_empty() {
+                    to_return.push_str(RESET);
+                }
+                to_return.push_str(next);
+                current = next;
+            }
+            to_return.push(c);
+        }
+        if !current.is_empty() {
+            to_return.push_str(RESET);
+        }
+        to_return
+    }
+    pub fn render(&self, colour: bool) -> String {
+        let line = if colour { self.highlighted() } else { String::from(self.line) };
+        format!("{}  => {}", line, self.summary())
+    }
+}
+
+impl Decoder {
+    pub fn explain<'a>(&self, line: &'a str) -> Explanation<'a> {
+        let mut matches = self.matches(line);
+        matches.sort_by_key(|m| (m.start, m.end));
+        let (first, last) = match self.first_and_last(line) {
+            Some((first, last)) => (Some(first), Some(last)),
+            None => (None, None),
+        };
+        Explanation { line, matches, first, last, value: self.decode(line) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colour {
+    Auto,
+    Always,
+    Never,
+}
+impl Colour {
+    pub fn enabled(&self) -> bool {
+        match self {
+            Colour::Always => true,
+            Colour::Never => false,
+            Colour::Auto => std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+// Prints an explanation of every line of every source. Returns whether every line decoded.
+pub fn print(decoder: &Decoder, sources: &[Source], colour: bool) -> bool {
+    let mut success = true;
+    for source in sources {
+        if sources.len() > 1 {
+            println!("==> {} <==", source);
+        }
+        let lines = match source.read_lines() {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("error: {}: {}", source, e);
+                success = false;
+                continue;
+            },
+        };
+        let width = lines.len().to_string().len();
+        for (i, line) in lines.iter().enumerate() {
+            let explanation = decoder.explain(line);
+            success &= explanation.value.is_ok();
+            println!("{:>width$} | {}", i + 1, explanation.render(colour), width = width);
+        }
+    }
+    success
+}
+
+// Decodes a whole document, like part 2 but with any decoder.
+pub fn explain_document<'a>(decoder: &Decoder, lines: &[&'a str]) -> Vec<Explanation<'a>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut explanation = decoder.explain(line);
+            explanation.value = explanation.value.map_err(|e| e.on_line(i + 1));
+            explanation
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DigitVocabulary;
+
+    #[test]
+    fn explains_overlaps() {
+        let decoder = DigitVocabulary::english().decoder();
+        let explanation = decoder.explain("xeightwo");
+        let spans: Vec<(usize, usize, u32)> = explanation.matches.iter().map(|m| (m.start, m.end, m.value)).collect();
+        assert_eq!(vec![(1, 6, 8), (5, 8, 2)], spans);
+        assert_eq!(Some((1, 6)), explanation.first.map(|m| (m.start, m.end)));
+        assert_eq!(Some((5, 8)), explanation.last.map(|m| (m.start, m.end)));
+        assert_eq!(Ok(82), explanation.value);
+        assert_eq!("xeightwo  => 82 (first \"eight\" at 1..6, last \"two\" at 5..8)", explanation.render(false));
+    }
+
+    #[test]
+    fn highlights() {
+        let decoder = DigitVocabulary::english().decoder();
+        let expected = format!("a{}one{}{}2{}{}three{}x", FIRST, RESET, OTHER, RESET, LAST, RESET);
+        assert_eq!(expected, decoder.explain("aone2threex").highlighted());
+        let expected = format!("{}eigh{}{}t{}{}wo{}", FIRST, RESET, BOTH, RESET, LAST, RESET);
+        assert_eq!(expected, decoder.explain("eightwo").highlighted());
+    }
+
+    #[test]
+    fn explains_errors() {
+        let decoder = DigitVocabulary::english().decoder();
+        let explanations = explain_document(&decoder, &["1abc", "abc"]);
+        assert_eq!(Ok(11), explanations[0].value);
+        assert_eq!(2, explanations[1].value.as_ref().unwrap_err().line);
+        assert!(explanations[1].matches.is_empty());
+        assert_eq!("abc  => error: missing a digit or the name of one", explanations[1].render(true));
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::Rng;
+
+use crate::part2::STRING_DIGITS;
+
+fn token(rng: &mut Rng) -> String {
+    match rng.range(0..3) {
+        0 => rng.range(0..10).to_string(),
+        // The first nine are the spelled out ones.
+        1 => String::from(rng.choose(&STRING_DIGITS[..9]).0),
+        _ => (0..rng.range(1..6)).map(|_| (b'a' + rng.range(0..26) as u8) as char).collect(),
+    }
+}
+
+// Calibration lines mixing letters, digits and spelled out digits. Every line gets
+// at least one ASCII digit so that part 1 can always solve it.
+pub fn calibration_line(rng: &mut Rng) -> String {
+    let mut tokens: Vec<String> = (0..rng.range(0..6)).map(|_| token(rng)).collect();
+    let digit_at = rng.range(0..(tokens.len() + 1));
+    tokens.insert(digit_at, rng.range(1..10).to_string());
+    tokens.concat()
+}
+
+pub fn generate(rng: &mut Rng, lines: usize) -> Vec<String> {
+    (0..lines.max(1)).map(|_| calibration_line(rng)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let lines = generate(&mut Rng::new(seed), 1 + seed as usize * 3);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert!(lines.iter().all(|l| l.chars().any(|c| c.is_ascii_digit())));
+            solve_part1(&lines_str[..]).unwrap();
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
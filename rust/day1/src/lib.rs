@@ -0,0 +1,40 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod cli;
+pub mod explain;
+pub mod generate;
+pub mod part1;
+pub mod part2;
+pub mod scanner;
+pub mod stream;
+pub mod vocabulary;
+
+pub use vocabulary::{ Decoder, DigitVocabulary };
+
+pub fn solve_part1(document: &[&str]) -> Result<u32, ParseError> {
+    Ok(part1::correct_calibration_document(document)?.into_iter().sum())
+}
+
+pub fn solve_part2(document: &[&str]) -> Result<u32, ParseError> {
+    Ok(part2::correct_calibration_document(document)?.into_iter().sum())
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        part1::correct_calibration_document(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
@@ -1,42 +1,23 @@
-use std::io;
+use day1::cli::{ self, Options };
 
-use part1::NoNumbersPresent;
-
-mod part1;
-mod part2;
-
-fn main() -> io::Result<()> {
-    let lines: Vec<String> = io::stdin()
-        .lines()
-        .collect::<Result<_, _>>()?;
-    if lines.is_empty() {
-        eprintln!("Nothing found in stdin!");
-        std::process::exit(1);
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}\n\n{}", aoc_common::cli::DAY_USAGE, cli::USAGE);
+        return
     }
-    let lines_ref: Vec<&str> = lines.iter().map(|s| s.as_ref()).collect();
-
-    // Part 1.
-    {
-        let corrected_document = match part1::correct_calibration_document(&lines_ref[..]) {
-            Ok(l) => l,
-            Err(NoNumbersPresent) => {
-                eprintln!("No numbers were present in one of the lines of the document.");
-                std::process::exit(1);
-            },
-        };
-        println!("{}", corrected_document.into_iter().sum::<u32>());
+    if !args.iter().any(|a| a == "--explain" || a == "--stream") {
+        aoc_common::cli::run_day(&day1::Day1);
+        return
     }
-
-    // Part 2.
-    {
-        let corrected_document = match part2::correct_calibration_document(&lines_ref[..]) {
-            Ok(l) => l,
-            Err(NoNumbersPresent) => {
-                eprintln!("No numbers were present in one of the lines of the document.");
-                std::process::exit(1);
-            },
-        };
-        println!("{}", corrected_document.into_iter().sum::<u32>());
+    let options = match Options::parse(args.into_iter()) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        },
+    };
+    if !cli::run(&options) {
+        std::process::exit(1);
     }
-    Ok(())
 }
@@ -1,12 +1,16 @@
-#[derive(Debug)]
-pub struct NoNumbersPresent;
+use aoc_common::{ parse, ParseError, ParseErrorKind };
 
-fn decode_calibration_value(s: &str) -> Result<u32, NoNumbersPresent> {
+// Lines without any digits point at the whole line.
+pub fn no_digits(line: &str, expected: &str) -> ParseError {
+    ParseError::at(line, line, ParseErrorKind::Missing { expected: String::from(expected) })
+}
+
+pub fn decode_calibration_value(s: &str) -> Result<u32, ParseError> {
     let numbers: Vec<char> = s.chars()
         .filter(|c| c.is_ascii_digit())
         .collect();
     match (numbers.first(), numbers.last()) {
-        (None, _) | (_, None) => Err(NoNumbersPresent),
+        (None, _) | (_, None) => Err(no_digits(s, "a digit")),
         (Some(first), Some(last)) => {
             let temp_string = format!("{}{}", first, last);
             // Unwrapping here is safe because first and last were checked to be ASCII digits.
@@ -15,10 +19,8 @@ fn decode_calibration_value(s: &str) -> Result<u32, NoNumbersPresent> {
     }
 }
 
-pub fn correct_calibration_document(l: &[&str]) -> Result<Vec<u32>, NoNumbersPresent> {
-    l.iter()
-        .map(|s| Ok(decode_calibration_value(s)?))
-        .collect()
+pub fn correct_calibration_document(l: &[&str]) -> Result<Vec<u32>, ParseError> {
+    parse::parse_lines(l, decode_calibration_value)
 }
 
 #[cfg(test)]
@@ -27,7 +29,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let document = vec![
+        let document = [
             "1abc2",
             "pqr3stu8vwx",
             "a1b2c3d4e5f",
@@ -41,4 +43,13 @@ mod tests {
         let expected_sum: u32 = expected_nums.iter().sum();
         assert_eq!(sum, expected_sum);
     }
+
+    #[test]
+    fn line_without_digits() {
+        let document = ["1abc2", "pqrstu"];
+        let error = correct_calibration_document(&document[..]).unwrap_err();
+        assert_eq!(2, error.line);
+        assert_eq!(1, error.column);
+        assert_eq!(6, error.len);
+    }
 }
@@ -1,111 +1,34 @@
-use std::{str::FromStr, fmt::Display};
+use aoc_common::{ parse, ParseError };
 
-use crate::part1;
+use crate::vocabulary::{ Decoder, DigitVocabulary };
 
-#[derive(Debug)]
-enum DigitParseError {
-    TooManyDigits,
-    NotInt(std::num::ParseIntError)
-}
-
-struct Digit(u32);
-impl FromStr for Digit {
-    type Err = DigitParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() == 1 && s[0..1].parse::<u32>().is_ok() {
-            return Ok(Digit(s[0..1].parse::<u32>().unwrap()));
-        }
-        match s.to_lowercase().as_str() {
-            "one" => Ok(Digit(1)),
-            "two" => Ok(Digit(2)),
-            "three" => Ok(Digit(3)),
-            "four" => Ok(Digit(4)),
-            "five" => Ok(Digit(5)),
-            "six" => Ok(Digit(6)),
-            "seven" => Ok(Digit(7)),
-            "eight" => Ok(Digit(8)),
-            "nine" => Ok(Digit(9)),
-            _ => match s.parse::<u32>() {
-                Ok(_) => Err(DigitParseError::TooManyDigits),
-                Err(e) => Err(DigitParseError::NotInt(e)),
-            },
-        }
-    }
-}
-impl Display for Digit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-impl Digit {
-    fn as_num(&self) -> u32 {
-        self.0
-    }
-}
-
-const STRING_DIGITS: [&str; 18] = [
-    "one",
-    "two",
-    "three",
-    "four",
-    "five",
-    "six",
-    "seven",
-    "eight",
-    "nine",
-    "1",
-    "2",
-    "3",
-    "4",
-    "5",
-    "6",
-    "7",
-    "8",
-    "9",
+pub(crate) const STRING_DIGITS: [(&str, u32); 18] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
 ];
 
-type Index = usize;
-
-fn decode_calibration_value(s: &str) -> Result<u32, part1::NoNumbersPresent> {
-    // This will keep searching a string with the current query to find all occurrances.
-    fn go(s: &str, to_find: &str) -> Vec<Index> {
-        let mut find_result = s.find(to_find);
-        let mut to_return: Vec<Index> = Vec::new();
-        let mut offset: usize = 0;
-        while find_result.is_some() {
-            let latest_result = find_result.unwrap();
-            to_return.push(latest_result + offset);
-            if latest_result + offset + 1 >= s.len() {
-                break;
-            }
-            else {
-                find_result = (&s[(latest_result + 1 + offset)..(s.len())]).find(to_find);
-                offset = latest_result + offset + 1;
-            }
-        }
-        to_return
-    }
-    let mut findings: Vec<(Index, Digit)> = Vec::new();
-    for d in STRING_DIGITS {
-        findings.extend(go(s, d).iter().map(|i| (*i, Digit::from_str(d).unwrap())));
-    }
-    if findings.is_empty() {
-        Err(part1::NoNumbersPresent)
-    }
-    else if findings.len() == 1 {
-        Ok(format!("{}{}", findings[0].1.as_num(), findings[0].1.as_num()).parse::<u32>().unwrap())
-    }
-    else {
-        let first = findings.iter().min_by_key(|tuple| tuple.0).unwrap();
-        let last = findings.iter().max_by_key(|tuple| tuple.0).unwrap();
-        Ok(format!("{}{}", first.1.to_string(), last.1.to_string()).parse::<u32>().unwrap())
-    }
+pub fn correct_calibration_document_with(decoder: &Decoder, l: &[&str]) -> Result<Vec<u32>, ParseError> {
+    parse::parse_lines(l, |line| decoder.decode(line))
 }
 
-pub fn correct_calibration_document(l: &[&str]) -> Result<Vec<u32>, part1::NoNumbersPresent> {
-    l.iter()
-        .map(|s| Ok(decode_calibration_value(s)?))
-        .collect()
+pub fn correct_calibration_document(l: &[&str]) -> Result<Vec<u32>, ParseError> {
+    correct_calibration_document_with(&DigitVocabulary::english().decoder(), l)
 }
 
 #[cfg(test)]
@@ -114,7 +37,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let document = vec![
+        let document = [
             "two1nine",
             "eightwothree",
             "abcone2threexyz",
@@ -134,9 +57,15 @@ mod tests {
     
     #[test]
     fn tricky_one() {
-        let document = vec!["5ffour295"];
+        let document = ["5ffour295"];
         let decoded_document = correct_calibration_document(&document[..]).unwrap();
         let sum: u32 = decoded_document.iter().sum();
         assert_eq!(55, sum);
     }
+
+    #[test]
+    fn overlapping_words() {
+        let document = ["eightwo", "oneight", "twone"];
+        assert_eq!(vec![82, 18, 21], correct_calibration_document(&document[..]).unwrap());
+    }
 }
@@ -0,0 +1,227 @@
+use std::collections::VecDeque;
+
+const ALPHABET: usize = 256;
+
+// Where a pattern was found, as a byte range of the line, and what it's worth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+impl Match {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+// An Aho-Corasick automaton: every pattern is found in a single pass over the input,
+// overlapping matches included, so "eightwo" gives both eight and two.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scanner {
+    // The state to go to on each byte, with failures already followed so scanning never backs up.
+    transitions: Vec<[usize; ALPHABET]>,
+    // Every pattern ending in each state, as (length in bytes, value).
+    outputs: Vec<Vec<(usize, u32)>>,
+    ignore_ascii_case: bool,
+}
+impl Scanner {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>) -> Scanner {
+        Scanner::build(patterns, false)
+    }
+    // Only ASCII letters are folded, so that every match is still a byte range of the original line.
+    pub fn ignoring_ascii_case<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>) -> Scanner {
+        Scanner::build(patterns, true)
+    }
+    fn build<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>, ignore_ascii_case: bool) -> Scanner {
+        // First a trie of the patterns.
+        let mut trie: Vec<[Option<usize>; ALPHABET]> = vec![[None; ALPHABET]];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![Vec::new()];
+        for (pattern, value) in patterns {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for byte in pattern.bytes() {
+                let byte = if ignore_ascii_case { byte.to_ascii_lowercase() } else { byte };
+                state = match trie[state][byte as usize] {
+                    Some(next) => next,
+                    None => {
+                        trie.push([None; ALPHABET]);
+                        outputs.push(Vec::new());
+                        trie[state][byte as usize] = Some(trie.len() - 1);
+                        trie.len() - 1
+                    },
+                };
+            }
+            outputs[state].push((pattern.len(), value));
+        }
+
+        // Then the failure of each state, breadth first so that shallower states are always done first.
+        let mut transitions: Vec<[usize; ALPHABET]> = vec![[0; ALPHABET]; trie.len()];
+        let mut failures: Vec<usize> = vec![0; trie.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for byte in 0..ALPHABET {
+            if let Some(next) = trie[0][byte] {
+                transitions[0][byte] = next;
+                queue.push_back(next);
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[failures[state]].clone();
+            outputs[state].extend(inherited);
+            for byte in 0..ALPHABET {
+                match trie[state][byte] {
+                    Some(next) => {
+                        failures[next] = transitions[failures[state]][byte];
+                        transitions[state][byte] = next;
+                        queue.push_back(next);
+                    },
+                    None => transitions[state][byte] = transitions[failures[state]][byte],
+                }
+            }
+        }
+        Scanner { transitions, outputs, ignore_ascii_case }
+    }
+
+    // Every match in `haystack`, in the order they end.
+    pub fn matches<'s, 'h>(&'s self, haystack: &'h str) -> Matches<'s, 'h> {
+        Matches { scanner: self, haystack: haystack.as_bytes(), position: 0, state: 0, output: 0 }
+    }
+
+    // Matches that don't overlap, taken from the left and preferring the longest, so "eightwo" is just eight.
+    pub fn leftmost_longest(&self, haystack: &str) -> Vec<Match> {
+        let mut matches: Vec<Match> = self.matches(haystack).collect();
+        matches.sort_by_key(|m| (m.start, std::cmp::Reverse(m.len())));
+        let mut to_return: Vec<Match> = Vec::new();
+        for m in matches {
+            if to_return.last().is_none_or(|previous| m.start >= previous.end) {
+                to_return.push(m);
+            }
+        }
+        to_return
+    }
+
+    // The match starting first and the one starting last, preferring longer matches on a tie.
+    pub fn first_and_last(&self, haystack: &str) -> Option<(Match, Match)> {
+        let longer = |a: &Match, b: &Match| a.start == b.start && a.len() > b.len();
+        let mut to_return: Option<(Match, Match)> = None;
+        for m in self.matches(haystack) {
+            to_return = Some(match to_return {
+                None => (m, m),
+                Some((first, last)) => (
+                    if m.start < first.start || longer(&m, &first) { m } else { first },
+                    if m.start > last.start || longer(&m, &last) { m } else { last },
+                ),
+            });
+        }
+        to_return
+    }
+}
+
+pub struct Matches<'s, 'h> {
+    scanner: &'s Scanner,
+    haystack: &'h [u8],
+    position: usize,
+    state: usize,
+    // How many of the current state's outputs have been given out.
+    output: usize,
+}
+impl Iterator for Matches<'_, '_> {
+    type Item = Match;
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(&(len, value)) = self.scanner.outputs[self.state].get(self.output) {
+                self.output += 1;
+                return Some(Match { start: self.position - len, end: self.position, value });
+            }
+            let mut byte = *self.haystack.get(self.position)?;
+            if self.scanner.ignore_ascii_case {
+                byte = byte.to_ascii_lowercase();
+            }
+            self.state = self.scanner.transitions[self.state][byte as usize];
+            self.position += 1;
+            self.output = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ generate::calibration_line, part2::STRING_DIGITS };
+    use aoc_common::Rng;
+
+    // Tries every pattern at every position.
+    fn naive_matches(patterns: &[(&str, u32)], haystack: &str) -> Vec<Match> {
+        let mut to_return: Vec<Match> = Vec::new();
+        for start in 0..haystack.len() {
+            for (pattern, value) in patterns {
+                if haystack.as_bytes()[start..].starts_with(pattern.as_bytes()) {
+                    to_return.push(Match { start, end: start + pattern.len(), value: *value });
+                }
+            }
+        }
+        to_return
+    }
+
+    fn sorted(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|m| (m.start, m.end, m.value));
+        matches
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = Scanner::new(STRING_DIGITS);
+        let found: Vec<(usize, u32)> = scanner.matches("xeightwone3").map(|m| (m.start, m.value)).collect();
+        assert_eq!(vec![(1, 8), (5, 2), (7, 1), (10, 3)], found);
+        assert_eq!(0, scanner.matches("no digits here").count());
+    }
+
+    #[test]
+    fn patterns_inside_patterns() {
+        let patterns = [("he", 1), ("she", 2), ("his", 3), ("hers", 4), ("e", 5)];
+        let scanner = Scanner::new(patterns);
+        for haystack in ["ushers", "shishers", "", "eeee"] {
+            let found: Vec<Match> = scanner.matches(haystack).collect();
+            assert_eq!(sorted(naive_matches(&patterns, haystack)), sorted(found));
+        }
+    }
+
+    #[test]
+    fn agrees_with_naive_search() {
+        let scanner = Scanner::new(STRING_DIGITS);
+        let mut rng = Rng::new(2023);
+        for _ in 0..500 {
+            let line = calibration_line(&mut rng);
+            let found: Vec<Match> = scanner.matches(&line).collect();
+            assert_eq!(sorted(naive_matches(&STRING_DIGITS, &line)), sorted(found));
+        }
+    }
+
+    #[test]
+    fn ignoring_case() {
+        let scanner = Scanner::ignoring_ascii_case([("One", 1), ("two", 2)]);
+        let found: Vec<u32> = scanner.matches("ONE tWo one").map(|m| m.value).collect();
+        assert_eq!(vec![1, 2, 1], found);
+        assert_eq!(0, Scanner::new([("one", 1)]).matches("ONE").count());
+    }
+
+    #[test]
+    fn without_overlaps() {
+        let scanner = Scanner::new(STRING_DIGITS);
+        let found: Vec<u32> = scanner.leftmost_longest("eightwone3twone").iter().map(|m| m.value).collect();
+        assert_eq!(vec![8, 1, 3, 2], found);
+    }
+
+    #[test]
+    fn first_and_last_prefer_longer() {
+        let scanner = Scanner::new([("six", 6), ("sixteen", 16), ("teen", 10)]);
+        let (first, last) = scanner.first_and_last("sixteen").unwrap();
+        assert_eq!((16, 10), (first.value, last.value));
+        assert_eq!(None, scanner.first_and_last("seven"));
+    }
+}
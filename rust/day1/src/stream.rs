@@ -0,0 +1,281 @@
+use std::{ fmt::Display, io::BufRead, str::FromStr };
+
+use aoc_common::ParseError;
+
+// A line that didn't decode, kept with its text so it can be quoted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offending {
+    pub error: ParseError,
+    pub text: String,
+}
+impl Offending {
+    pub fn diagnostic(&self, source_name: &str) -> String {
+        self.error.line_diagnostic(source_name, &self.text)
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(std::io::Error),
+    Parse(Offending),
+}
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "Couldn't read input: {}", e),
+            StreamError::Parse(o) => write!(f, "{}", o.error),
+        }
+    }
+}
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamError::Io(e) => Some(e),
+            StreamError::Parse(o) => Some(&o.error),
+        }
+    }
+}
+impl From<std::io::Error> for StreamError {
+    fn from(e: std::io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+// What to do with a line that doesn't decode.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ErrorPolicy {
+    // Stop at the first one.
+    #[default]
+    Strict,
+    // Leave it out of the sum.
+    Skip,
+    // Count it as this value instead.
+    Default(u32),
+}
+impl FromStr for ErrorPolicy {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(ErrorPolicy::Strict),
+            "skip" => Ok(ErrorPolicy::Skip),
+            _ => match s.strip_prefix("default=") {
+                Some(value) => value
+                    .parse()
+                    .map(ErrorPolicy::Default)
+                    .map_err(|e| format!("\"{}\" is not a valid default value: {}", value, e)),
+                None => Err(format!("\"{}\" is not strict, skip or default=<N>", s)),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    // Wider than the values, so a long enough document of them can't overflow it.
+    pub total: u64,
+    pub lines: usize,
+    // Every line that was skipped or given the default value.
+    pub offending: Vec<Offending>,
+}
+
+// Anything that can be a line: plain strings, or the results of `BufRead::lines`.
+pub trait IntoLine {
+    type Text: AsRef<str>;
+    fn into_line(self) -> std::io::Result<Self::Text>;
+}
+impl IntoLine for String {
+    type Text = String;
+    fn into_line(self) -> std::io::Result<String> {
+        Ok(self)
+    }
+}
+impl<'a> IntoLine for &'a str {
+    type Text = &'a str;
+    fn into_line(self) -> std::io::Result<&'a str> {
+        Ok(self)
+    }
+}
+impl IntoLine for std::io::Result<String> {
+    type Text = String;
+    fn into_line(self) -> std::io::Result<String> {
+        self
+    }
+}
+
+// Decodes lines one at a time, so only the current line is ever held in memory.
+pub struct Values<I, F> {
+    lines: I,
+    decode: F,
+    line: usize,
+    policy: ErrorPolicy,
+    offending: Vec<Offending>,
+}
+impl<I, F> Values<I, F>
+where
+    I: Iterator,
+    I::Item: IntoLine,
+    F: Fn(&str) -> Result<u32, ParseError>,
+{
+    pub fn new(lines: impl IntoIterator<IntoIter = I>, decode: F) -> Values<I, F> {
+        Values { lines: lines.into_iter(), decode, line: 0, policy: ErrorPolicy::Strict, offending: Vec::new() }
+    }
+    pub fn on_error(mut self, policy: ErrorPolicy) -> Values<I, F> {
+        self.policy = policy;
+        self
+    }
+    // How many lines have been read so far.
+    pub fn lines_read(&self) -> usize {
+        self.line
+    }
+    // The sum so far after every line.
+    pub fn running_sums(self) -> impl Iterator<Item = Result<u64, StreamError>> {
+        self.scan(0, |total, value| Some(value.map(|v| {
+            *total += u64::from(v);
+            *total
+        })))
+    }
+    pub fn total(self) -> Result<u64, StreamError> {
+        self.map(|value| value.map(u64::from)).sum()
+    }
+    // Lines that didn't decode but were let through by the policy.
+    pub fn offending(&self) -> &[Offending] {
+        &self.offending[..]
+    }
+    pub fn summarise(mut self) -> Result<Summary, StreamError> {
+        let total = self.by_ref().map(|value| value.map(u64::from)).sum::<Result<u64, StreamError>>()?;
+        Ok(Summary { total, lines: self.line, offending: self.offending })
+    }
+}
+impl<I, F> Iterator for Values<I, F>
+where
+    I: Iterator,
+    I::Item: IntoLine,
+    F: Fn(&str) -> Result<u32, ParseError>,
+{
+    type Item = Result<u32, StreamError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line += 1;
+            let text = match line.into_line() {
+                Ok(t) => t,
+                Err(e) => return Some(Err(StreamError::Io(e))),
+            };
+            let error = match (self.decode)(text.as_ref()) {
+                Ok(value) => return Some(Ok(value)),
+                Err(e) => e.on_line(self.line),
+            };
+            let offending = Offending { error, text: String::from(text.as_ref()) };
+            match self.policy {
+                ErrorPolicy::Strict => return Some(Err(StreamError::Parse(offending))),
+                ErrorPolicy::Skip => self.offending.push(offending),
+                ErrorPolicy::Default(value) => {
+                    self.offending.push(offending);
+                    return Some(Ok(value))
+                },
+            }
+        }
+    }
+}
+
+pub fn values<R: BufRead, F>(reader: R, decode: F) -> Values<std::io::Lines<R>, F>
+where
+    F: Fn(&str) -> Result<u32, ParseError>,
+{
+    Values::new(reader.lines(), decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ part1, DigitVocabulary };
+
+    const DOCUMENT: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+
+    #[test]
+    fn streams_values() {
+        let decoder = DigitVocabulary::english().decoder();
+        let decoded: Vec<u32> = values(DOCUMENT.as_bytes(), |l| decoder.decode(l))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![29, 83, 13, 24, 42, 14, 76], decoded);
+        assert_eq!(281, values(DOCUMENT.as_bytes(), |l| decoder.decode(l)).total().unwrap());
+    }
+
+    #[test]
+    fn running_sums() {
+        let sums: Vec<u64> = Values::new(["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f"], part1::decode_calibration_value)
+            .running_sums()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![12, 50, 65], sums);
+    }
+
+    #[test]
+    fn sums_past_u32() {
+        let biggest = |_: &str| -> Result<u32, ParseError> { Ok(u32::MAX) };
+        let expected = 3 * u64::from(u32::MAX);
+        assert_eq!(expected, Values::new(["a", "b", "c"], biggest).total().unwrap());
+        assert_eq!(Some(expected), Values::new(["a", "b", "c"], biggest).running_sums().last().map(|s| s.unwrap()));
+        assert_eq!(expected, Values::new(["a", "b", "c"], biggest).summarise().unwrap().total);
+    }
+
+    #[test]
+    fn numbers_errors() {
+        let lines = vec![String::from("1abc2"), String::from("abc")];
+        let mut values = Values::new(lines, part1::decode_calibration_value);
+        assert_eq!(12, values.next().unwrap().unwrap());
+        match values.next() {
+            Some(Err(StreamError::Parse(o))) => {
+                assert_eq!(2, o.error.line);
+                assert_eq!("abc", o.text);
+            },
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert_eq!(2, values.lines_read());
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn policies() {
+        let document = ["1abc2", "abc", "treb7uchet", "xyz"];
+        let summarise = |policy| Values::new(document, part1::decode_calibration_value).on_error(policy).summarise();
+
+        let skipped = summarise(ErrorPolicy::Skip).unwrap();
+        assert_eq!(89, skipped.total);
+        assert_eq!(4, skipped.lines);
+        let lines: Vec<(usize, &str)> = skipped.offending.iter().map(|o| (o.error.line, o.text.as_str())).collect();
+        assert_eq!(vec![(2, "abc"), (4, "xyz")], lines);
+
+        let defaulted = summarise(ErrorPolicy::Default(5)).unwrap();
+        assert_eq!(99, defaulted.total);
+        assert_eq!(skipped.offending, defaulted.offending);
+
+        match summarise(ErrorPolicy::Strict) {
+            Err(StreamError::Parse(o)) => assert_eq!("error: missing a digit\n --> doc:2:1\n  |\n2 | abc\n  | ^^^\n", o.diagnostic("doc")),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn policies_past_u32() {
+        let document = ["9", "abc", "xyz"];
+        let summarise = |policy| Values::new(document, part1::decode_calibration_value).on_error(policy).summarise();
+        let defaulted = summarise(ErrorPolicy::Default(u32::MAX)).unwrap();
+        assert_eq!(99 + 2 * u64::from(u32::MAX), defaulted.total);
+        assert_eq!(2, defaulted.offending.len());
+
+        let biggest = |l: &str| -> Result<u32, ParseError> { part1::decode_calibration_value(l).map(|_| u32::MAX) };
+        let skipped = Values::new(["1", "abc", "2"], biggest).on_error(ErrorPolicy::Skip).summarise().unwrap();
+        assert_eq!(2 * u64::from(u32::MAX), skipped.total);
+    }
+
+    #[test]
+    fn parses_policies() {
+        assert_eq!(Ok(ErrorPolicy::Strict), "strict".parse());
+        assert_eq!(Ok(ErrorPolicy::Skip), "skip".parse());
+        assert_eq!(Ok(ErrorPolicy::Default(0)), "default=0".parse());
+        assert!("default=x".parse::<ErrorPolicy>().is_err());
+        assert!("lenient".parse::<ErrorPolicy>().is_err());
+    }
+}
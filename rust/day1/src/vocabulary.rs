@@ -0,0 +1,257 @@
+use aoc_common::{ parse, ParseError, ParseErrorKind };
+
+use crate::{ part1, part2::STRING_DIGITS, scanner::{ Match, Scanner } };
+
+// The words that count as digits, and how to look for them. Values don't have to be single
+// digits: "ten" = 10 makes "one...ten" decode to 110, as if the words were written out as numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u32)>,
+    case_sensitive: bool,
+    overlapping: bool,
+}
+impl Default for DigitVocabulary {
+    fn default() -> Self {
+        DigitVocabulary::english()
+    }
+}
+impl DigitVocabulary {
+    // No words yet, case sensitive, and overlapping words like "eightwo" both count.
+    pub fn new() -> DigitVocabulary {
+        DigitVocabulary { words: Vec::new(), case_sensitive: true, overlapping: true }
+    }
+    // "one" to "nine" and "1" to "9", which is what the puzzle uses.
+    pub fn english() -> DigitVocabulary {
+        STRING_DIGITS.iter().fold(DigitVocabulary::new(), |v, (word, value)| v.word(word, *value))
+    }
+    pub fn word(mut self, word: &str, value: u32) -> DigitVocabulary {
+        self.words.push((String::from(word), value));
+        self
+    }
+    // Adds "0" to "9".
+    pub fn ascii_digits(self) -> DigitVocabulary {
+        (0..10).fold(self, |v, n| v.word(&n.to_string(), n))
+    }
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> DigitVocabulary {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+    pub fn overlapping(mut self, overlapping: bool) -> DigitVocabulary {
+        self.overlapping = overlapping;
+        self
+    }
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words[..]
+    }
+
+    pub fn decoder(&self) -> Decoder {
+        let patterns = self.words.iter().map(|(word, value)| (word.as_str(), *value));
+        let scanner = match self.case_sensitive {
+            true => Scanner::new(patterns),
+            false => Scanner::ignoring_ascii_case(patterns),
+        };
+        Decoder { scanner, overlapping: self.overlapping }
+    }
+
+    // Reads a vocabulary file like:
+    //
+    //     # French, with the options before the words
+    //     case_sensitive = false
+    //     overlapping = true
+    //
+    //     [words]
+    //     un = 1
+    //     "dix-sept" = 17
+    pub fn from_lines(lines: &[&str]) -> Result<DigitVocabulary, ParseError> {
+        let mut vocabulary = DigitVocabulary::new();
+        let mut in_words = false;
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let mut parse_line = || -> Result<(), ParseError> {
+                if trimmed == "[words]" {
+                    in_words = true;
+                    return Ok(());
+                }
+                let (key, value) = split_entry(line, trimmed)?;
+                if in_words {
+                    let number: u32 = parse::parse_number(line, value)?;
+                    // Repeating a word is fine as long as it means the same thing both times.
+                    match vocabulary.words.iter().find(|(w, _)| vocabulary.same_word(w, &key)) {
+                        Some((word, existing)) if *existing != number => {
+                            return Err(parse::invalid(line, value, format!("\"{}\" is already {}", word, existing)));
+                        },
+                        Some(_) => (),
+                        None => vocabulary.words.push((key, number)),
+                    }
+                    return Ok(());
+                }
+                let flag = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(parse::expected(line, value, "true or false")),
+                };
+                match key.as_str() {
+                    "case_sensitive" => vocabulary.case_sensitive = flag,
+                    "overlapping" => vocabulary.overlapping = flag,
+                    _ => return Err(parse::expected(line, trimmed, "case_sensitive, overlapping or [words]")),
+                }
+                Ok(())
+            };
+            parse_line().map_err(|e| e.on_line(i + 1))?;
+        }
+        if vocabulary.words.is_empty() {
+            let last_line = lines.last().copied().unwrap_or("");
+            return Err(parse::missing_after(last_line, last_line, "a [words] section with at least one word")
+                .on_line(lines.len().max(1)));
+        }
+        Ok(vocabulary)
+    }
+
+    // Whether the scanner would treat `a` and `b` as the same word.
+    fn same_word(&self, a: &str, b: &str) -> bool {
+        match self.case_sensitive {
+            true => a == b,
+            false => a.eq_ignore_ascii_case(b),
+        }
+    }
+}
+
+// Splits "key = value", where the key may be quoted to hold spaces, '=' or '#'.
+fn split_entry<'a>(line: &str, s: &'a str) -> Result<(String, &'a str), ParseError> {
+    let (key, rest) = match s.strip_prefix('"') {
+        Some(quoted) => {
+            let (key, rest) = parse::split_once(line, quoted, '"')?;
+            (String::from(key), parse::expect_prefix(line, rest.trim_start(), "=")?)
+        },
+        None => {
+            let (key, rest) = parse::split_once(line, s, '=')?;
+            (String::from(key.trim()), rest)
+        },
+    };
+    if key.is_empty() {
+        return Err(parse::missing_after(line, &s[..0], "a word"));
+    }
+    let value = rest.split('#').next().unwrap_or("").trim();
+    if value.is_empty() {
+        return Err(parse::missing_after(line, rest, "a value"));
+    }
+    Ok((key, value))
+}
+
+// Writes the two values next to each other, e.g. 1 and 10 make 110.
+fn concatenate(first: u32, last: u32) -> Option<u32> {
+    let digits = last.checked_ilog10().unwrap_or(0) + 1;
+    first.checked_mul(10_u32.checked_pow(digits)?)?.checked_add(last)
+}
+
+// A vocabulary ready to decode lines with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoder {
+    scanner: Scanner,
+    overlapping: bool,
+}
+impl Decoder {
+    // The tokens that count: all of them, or only those that don't overlap.
+    pub fn matches(&self, line: &str) -> Vec<Match> {
+        match self.overlapping {
+            true => self.scanner.matches(line).collect(),
+            false => self.scanner.leftmost_longest(line),
+        }
+    }
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        if self.overlapping {
+            return self.scanner.first_and_last(line);
+        }
+        let matches = self.scanner.leftmost_longest(line);
+        Some((*matches.first()?, *matches.last()?))
+    }
+    pub fn decode(&self, line: &str) -> Result<u32, ParseError> {
+        let (first, last) = self
+            .first_and_last(line)
+            .ok_or_else(|| part1::no_digits(line, "a digit or the name of one"))?;
+        concatenate(first.value, last.value).ok_or_else(|| {
+            let span = &line[first.start.min(last.start)..first.end.max(last.end)];
+            ParseError::at(line, span, ParseErrorKind::InvalidNumber {
+                text: format!("{}{}", first.value, last.value),
+                reason: String::from("number too large to fit in target type"),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_is_the_puzzle() {
+        let decoder = DigitVocabulary::english().decoder();
+        assert_eq!(Ok(83), decoder.decode("eightwothree"));
+        assert_eq!(Ok(82), decoder.decode("eightwo"));
+        assert!(decoder.decode("EIGHT").is_err());
+    }
+
+    #[test]
+    fn builder_options() {
+        let decoder = DigitVocabulary::english().case_sensitive(false).overlapping(false).decoder();
+        assert_eq!(Ok(88), decoder.decode("EIGHTwo"));
+        assert_eq!(Ok(22), decoder.decode("twOne"));
+        let decoder = DigitVocabulary::new().ascii_digits().decoder();
+        assert_eq!(Ok(7), decoder.decode("zero0seven7"));
+    }
+
+    #[test]
+    fn bigger_values() {
+        let decoder = DigitVocabulary::new().word("ten", 10).word("XII", 12).word("one", 1).decoder();
+        assert_eq!(Ok(110), decoder.decode("one..ten"));
+        assert_eq!(Ok(1210), decoder.decode("XIIten"));
+        let decoder = DigitVocabulary::new().word("huge", 4_000_000_000).decoder();
+        assert!(decoder.decode("huge").is_err());
+    }
+
+    #[test]
+    fn from_file() {
+        let lines = [
+            "# German",
+            "case_sensitive = false",
+            "overlapping = false",
+            "",
+            "[words]",
+            "eins = 1",
+            "zwei = 2",
+            "\"drei # three\" = 3  # quoted",
+            "1 = 1",
+        ];
+        let vocabulary = DigitVocabulary::from_lines(&lines).unwrap();
+        assert_eq!(4, vocabulary.words().len());
+        let decoder = vocabulary.decoder();
+        assert_eq!(Ok(13), decoder.decode("EINSxDREI # THREE"));
+        assert_eq!(Ok(22), decoder.decode("zweins"));
+    }
+
+    #[test]
+    fn repeated_words() {
+        let vocabulary = DigitVocabulary::from_lines(&["case_sensitive = false", "[words]", "one = 1", "ONE = 1", "one = 1"]).unwrap();
+        assert_eq!(&[(String::from("one"), 1)], vocabulary.words());
+        assert_eq!(Ok(11), vocabulary.decoder().decode("xOnex"));
+        let vocabulary = DigitVocabulary::from_lines(&["[words]", "one = 1", "One = 2"]).unwrap();
+        assert_eq!(2, vocabulary.words().len());
+    }
+
+    #[test]
+    fn file_errors() {
+        assert_eq!(2, DigitVocabulary::from_lines(&["[words]", "one = x"]).unwrap_err().line);
+        assert!(DigitVocabulary::from_lines(&["[words]", "one = 1", "one = 2"]).is_err());
+        let error = DigitVocabulary::from_lines(&["case_sensitive = false", "[words]", "One = 1", "one = 2"]).unwrap_err();
+        assert_eq!((4, ParseErrorKind::Invalid(String::from("\"One\" is already 1"))), (error.line, error.kind));
+        assert!(DigitVocabulary::from_lines(&["case_sensitive = maybe"]).is_err());
+        assert!(DigitVocabulary::from_lines(&["colour = true", "[words]", "one = 1"]).is_err());
+        assert!(DigitVocabulary::from_lines(&["[words]", "one"]).is_err());
+        let error = DigitVocabulary::from_lines(&["[words]", "  = 1"]).unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert!(DigitVocabulary::from_lines(&["case_sensitive = true"]).is_err());
+    }
+}
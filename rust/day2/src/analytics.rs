@@ -0,0 +1,246 @@
+use aoc_common::report::json_string;
+
+use crate::{ part1::{ Game, Set }, part2::{ self, max_set_colours } };
+
+// A colour's count in the minimal bag, and the first set (counting from 1) that needed that many.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub colour: String,
+    pub count: usize,
+    pub set: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameReport {
+    pub id: usize,
+    pub bag: Set,
+    pub power: usize,
+    pub constraints: Vec<Constraint>,
+}
+
+// `palette` is the colours that count towards the power, usually `part2::palette` of every game.
+pub fn analyse_game(game: &Game, palette: &[&str]) -> GameReport {
+    let bag = max_set_colours(&game.revelations[..]);
+    let constraints = bag
+        .iter()
+        .map(|(colour, count)| Constraint {
+            colour: String::from(colour),
+            count,
+            // Unwrapping is safe because the maximum came from one of the sets.
+            set: game.revelations.iter().position(|s| s.get(colour) == Some(count)).unwrap() + 1,
+        })
+        .collect();
+    GameReport { id: game.id, power: part2::power(&bag, palette), bag, constraints }
+}
+
+pub fn analyse_games(games: &[Game]) -> Vec<GameReport> {
+    let palette = part2::palette(games);
+    games.iter().map(|g| analyse_game(g, &palette[..])).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub total_power: usize,
+    pub min: usize,
+    pub median: f64,
+    pub mean: f64,
+    pub max: usize,
+    // How many games have a power with each number of digits, from the fewest to the most.
+    pub histogram: Vec<(usize, usize)>,
+    // The colour that was the largest in a game's minimal bag most often, and how often.
+    pub most_constraining: Option<(String, usize)>,
+}
+
+fn digits(n: usize) -> usize {
+    n.checked_ilog10().map_or(0, |d| d as usize + 1)
+}
+
+fn bucket_label(digits: usize) -> String {
+    match digits {
+        0 => String::from("0"),
+        d => format!("{}-{}", 10_usize.pow(d as u32 - 1), "9".repeat(d)),
+    }
+}
+
+pub fn summarise(reports: &[GameReport]) -> Summary {
+    let mut powers: Vec<usize> = reports.iter().map(|r| r.power).collect();
+    powers.sort_unstable();
+    let median = match powers.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (powers[n / 2 - 1] + powers[n / 2]) as f64 / 2.0,
+        n => powers[n / 2] as f64,
+    };
+    let total_power: usize = powers.iter().sum();
+
+    let mut histogram: Vec<(usize, usize)> = Vec::new();
+    if let (Some(first), Some(last)) = (powers.first(), powers.last()) {
+        histogram = (digits(*first)..=digits(*last)).map(|d| (d, 0)).collect();
+        for power in &powers {
+            histogram[digits(*power) - digits(*first)].1 += 1;
+        }
+    }
+
+    let mut largest: Vec<(String, usize)> = Vec::new();
+    for report in reports {
+        let Some(max) = report.bag.iter().map(|(_, n)| n).max() else {
+            continue;
+        };
+        for (colour, _) in report.bag.iter().filter(|(_, n)| *n == max) {
+            match largest.iter_mut().find(|(c, _)| c == colour) {
+                Some((_, games)) => *games += 1,
+                None => largest.push((String::from(colour), 1)),
+            }
+        }
+    }
+    // On a tie, the colour that was seen first wins.
+    let most_constraining = largest
+        .into_iter()
+        .fold(None, |best: Option<(String, usize)>, (colour, games)| match best {
+            Some((_, best_games)) if best_games >= games => best,
+            _ => Some((colour, games)),
+        });
+
+    Summary {
+        games: reports.len(),
+        total_power,
+        min: powers.first().copied().unwrap_or(0),
+        median,
+        mean: if powers.is_empty() { 0.0 } else { total_power as f64 / powers.len() as f64 },
+        max: powers.last().copied().unwrap_or(0),
+        histogram,
+        most_constraining,
+    }
+}
+
+pub fn render_text(reports: &[GameReport], summary: &Summary) -> String {
+    let mut to_return = String::new();
+    for report in reports {
+        to_return.push_str(&format!("Game {}: {}  power {}\n", report.id, report.bag, report.power));
+        let constraints: Vec<String> = report
+            .constraints
+            .iter()
+            .map(|c| format!("{} from set {}", c.colour, c.set))
+            .collect();
+        if !constraints.is_empty() {
+            to_return.push_str(&format!("  {}\n", constraints.join(", ")));
+        }
+    }
+    to_return.push_str(&format!("\n{} games, total power {}\n", summary.games, summary.total_power));
+    to_return.push_str(&format!(
+        "Power: min {}, median {}, mean {:.1}, max {}\n",
+        summary.min,
+        summary.median,
+        summary.mean,
+        summary.max,
+    ));
+    let width = summary.histogram.iter().map(|(d, _)| bucket_label(*d).len()).max().unwrap_or(0);
+    for (d, games) in &summary.histogram {
+        to_return.push_str(&format!("  {:>width$}: {}\n", bucket_label(*d), games, width = width));
+    }
+    if let Some((colour, games)) = &summary.most_constraining {
+        to_return.push_str(&format!(
+            "Most constraining colour: {} (the largest in {} of {} games)\n",
+            colour,
+            games,
+            summary.games,
+        ));
+    }
+    to_return
+}
+
+fn json_object<'a>(entries: impl Iterator<Item = (&'a str, String)>) -> String {
+    let entries: Vec<String> = entries.map(|(key, value)| format!("{}:{}", json_string(key), value)).collect();
+    format!("{{{}}}", entries.join(","))
+}
+
+pub fn render_json(reports: &[GameReport], summary: &Summary) -> String {
+    let games: Vec<String> = reports
+        .iter()
+        .map(|r| format!(
+            "{{\"id\":{},\"bag\":{},\"power\":{},\"forced_by\":{}}}",
+            r.id,
+            json_object(r.bag.iter().map(|(c, n)| (c, n.to_string()))),
+            r.power,
+            json_object(r.constraints.iter().map(|c| (c.colour.as_str(), c.set.to_string()))),
+        ))
+        .collect();
+    let histogram: Vec<String> = summary
+        .histogram
+        .iter()
+        .map(|(d, games)| format!("{{\"range\":{},\"games\":{}}}", json_string(&bucket_label(*d)), games))
+        .collect();
+    let most_constraining = match &summary.most_constraining {
+        Some((colour, games)) => format!("{{\"colour\":{},\"games\":{}}}", json_string(colour), games),
+        None => String::from("null"),
+    };
+    format!(
+        "{{\"games\":[\n{}\n],\"summary\":{{\"games\":{},\"total_power\":{},\"min\":{},\"median\":{},\"mean\":{},\"max\":{},\"histogram\":[{}],\"most_constraining\":{}}}}}",
+        games.join(",\n"),
+        summary.games,
+        summary.total_power,
+        summary.min,
+        summary.median,
+        summary.mean,
+        summary.max,
+        histogram.join(","),
+        most_constraining,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    const EXAMPLE: [&str; 5] = [
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    ];
+
+    fn reports() -> Vec<GameReport> {
+        analyse_games(&parse_games(&EXAMPLE).unwrap()[..])
+    }
+
+    #[test]
+    fn minimal_bags() {
+        let reports = reports();
+        assert_eq!(Set::from_counts(&[("red", 4), ("green", 2), ("blue", 6)]), reports[0].bag);
+        assert_eq!(48, reports[0].power);
+        let sets: Vec<(&str, usize)> = reports[0].constraints.iter().map(|c| (c.colour.as_str(), c.set)).collect();
+        assert_eq!(vec![("blue", 2), ("red", 1), ("green", 2)], sets);
+        assert_eq!(vec![48, 12, 1560, 630, 36], reports.iter().map(|r| r.power).collect::<Vec<usize>>());
+
+        let games = parse_games(&["Game 1: 2 yellow, 1 purple; 3 yellow", "Game 2: 4 purple"]).unwrap();
+        assert_eq!(vec![3, 0], analyse_games(&games[..]).iter().map(|r| r.power).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn summary() {
+        let summary = summarise(&reports()[..]);
+        assert_eq!(2286, summary.total_power);
+        assert_eq!((12, 48.0, 1560), (summary.min, summary.median, summary.max));
+        assert_eq!(vec![(2, 3), (3, 1), (4, 1)], summary.histogram);
+        // Blue is the largest in games 1, 2 and 4, red in 3 and 5.
+        assert_eq!(Some((String::from("blue"), 3)), summary.most_constraining);
+        assert_eq!("10-99", bucket_label(2));
+        assert_eq!("0", bucket_label(digits(0)));
+    }
+
+    #[test]
+    fn renders() {
+        let reports = reports();
+        let summary = summarise(&reports[..]);
+        let text = render_text(&reports[..1], &summary);
+        assert!(text.starts_with("Game 1: 6 blue, 4 red, 2 green  power 48\n  blue from set 2, red from set 1, green from set 2\n"));
+        assert!(text.contains("Power: min 12, median 48, mean 457.2, max 1560\n      10-99: 3\n"));
+        assert!(text.ends_with("Most constraining colour: blue (the largest in 3 of 5 games)\n"));
+
+        let json = render_json(&reports[..1], &summary);
+        assert!(json.starts_with("{\"games\":[\n{\"id\":1,\"bag\":{\"blue\":6,\"red\":4,\"green\":2},\"power\":48,\"forced_by\":{\"blue\":2,\"red\":1,\"green\":2}}\n]"));
+        assert!(json.ends_with("\"most_constraining\":{\"colour\":\"blue\",\"games\":3}}}"));
+    }
+}
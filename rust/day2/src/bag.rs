@@ -0,0 +1,120 @@
+use aoc_common::{ parse, ParseError };
+
+use crate::part1::{ default_bag, is_game_possible_with, parse_set, Game, Set };
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bag {
+    pub name: String,
+    pub cubes: Set,
+}
+impl Default for Bag {
+    fn default() -> Self {
+        Bag { name: String::from("puzzle"), cubes: default_bag() }
+    }
+}
+
+// Either "12 red, 13 green" or, to give it a name, "small = 12 red, 13 green".
+// Without a name, the counts are the name.
+pub fn parse_bag(line: &str) -> Result<Bag, ParseError> {
+    let (name, counts) = match line.split_once('=') {
+        Some((name, _)) if name.trim().is_empty() => return Err(parse::missing_after(line, name, "a bag name")),
+        Some((name, counts)) => (name.trim(), counts),
+        None => (line.trim(), line),
+    };
+    Ok(Bag { name: String::from(name), cubes: parse_set(line, counts)? })
+}
+
+// One bag per line. Blank lines and lines starting with # are ignored.
+pub fn parse_bags(lines: &[&str]) -> Result<Vec<Bag>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .map(|(i, l)| parse_bag(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation<'a> {
+    pub bag: &'a Bag,
+    // The IDs of the games that could have been played with the bag.
+    pub possible: Vec<usize>,
+    pub games: usize,
+}
+impl Evaluation<'_> {
+    pub fn id_sum(&self) -> usize {
+        self.possible.iter().sum()
+    }
+}
+
+pub fn evaluate<'a>(games: &[Game], bags: &'a [Bag]) -> Vec<Evaluation<'a>> {
+    bags
+        .iter()
+        .map(|bag| Evaluation {
+            bag,
+            possible: games.iter().filter(|g| is_game_possible_with(g, &bag.cubes)).map(|g| g.id).collect(),
+            games: games.len(),
+        })
+        .collect()
+}
+
+pub fn render_evaluations(evaluations: &[Evaluation]) -> String {
+    let mut to_return = String::new();
+    for e in evaluations {
+        let ids: Vec<String> = e.possible.iter().map(|id| id.to_string()).collect();
+        to_return.push_str(&format!(
+            "{}: {} of {} games possible, IDs sum to {}\n",
+            e.bag.name,
+            e.possible.len(),
+            e.games,
+            e.id_sum(),
+        ));
+        if !ids.is_empty() {
+            to_return.push_str(&format!("  {}\n", ids.join(", ")));
+        }
+    }
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    const EXAMPLE: [&str; 5] = [
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    ];
+
+    #[test]
+    fn parses_bags() {
+        let lines = ["# bags", "small = 4 red, 2 green, 6 blue", "", "20 red, 13 green, 15 blue"];
+        let bags = parse_bags(&lines).unwrap();
+        assert_eq!("small", bags[0].name);
+        assert_eq!(Set::from_counts(&[("red", 4), ("green", 2), ("blue", 6)]), bags[0].cubes);
+        assert_eq!("20 red, 13 green, 15 blue", bags[1].name);
+
+        let error = parse_bags(&["12 red", "big = 12 red, x blue"]).unwrap_err();
+        assert_eq!((2, 15), (error.line, error.column));
+        assert!(parse_bag(" = 12 red").is_err());
+    }
+
+    #[test]
+    fn many_bags() {
+        let games = parse_games(&EXAMPLE).unwrap();
+        let bags = [Bag::default(), parse_bag("big = 20 red, 13 green, 15 blue").unwrap()];
+        let evaluations = evaluate(&games[..], &bags);
+        assert_eq!(vec![1, 2, 5], evaluations[0].possible);
+        assert_eq!(8, evaluations[0].id_sum());
+        assert_eq!(vec![1, 2, 3, 4, 5], evaluations[1].possible);
+        let expected = "puzzle: 3 of 5 games possible, IDs sum to 8
+  1, 2, 5
+big: 5 of 5 games possible, IDs sum to 15
+  1, 2, 3, 4, 5
+";
+        assert_eq!(expected, render_evaluations(&evaluations[..]));
+    }
+}
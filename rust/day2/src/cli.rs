@@ -0,0 +1,236 @@
+use aoc_common::{ input, Format, Source };
+
+use crate::{ analytics, bag::{ self, Bag }, filter::{ self, Filter }, infer, parse_games, Game };
+
+pub const USAGE: &str = "Usage: day2 [--bag <COUNTS>]... [--bags <FILE>] [INPUT...]
+       day2 --report [--format <text|json>] [INPUT...]
+       day2 --filter <EXPRESSION> [INPUT...]
+       day2 --infer [--max <N>] [--top <N>] [INPUT...]
+
+--bag and --bags check which games could have been played with each bag, instead of solving both parts.
+A bag is written like a set of cubes, \"12 red, 13 green, 14 blue\", optionally named: \"small = 4 red, 2 green\".
+A bags file has one bag per line, and lines starting with # are ignored.
+
+--report shows each game's minimal bag, its power, and which set needed the most of each colour,
+followed by statistics over all the games.
+
+--filter prints the IDs of the games that match, one per line. For example:
+  \"any(red > 10 and blue < 3)\"   a set had more than 10 red and fewer than 3 blue
+  \"red <= 12 and power > 100\"    a game never showed more than 12 red, and its power is over 100
+  \"all(total < 10) or id == 3\"   every set had fewer than 10 cubes, or it's game 3
+In a game a colour is the most of it shown at once, and inside any(...) or all(...) it's the count in that set.
+Games have id, sets and power, and sets have total. Combine with and, or, not and brackets.
+
+--infer estimates what's really in the bag, taking each set as a draw without replacement from the same bag.
+It tries every bag with up to --max (default 20) of each colour, and shows the --top (default 5) most likely.
+It gives up without trying any if that would be more than ten million bags.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Bags,
+    Report,
+    Filter,
+    Infer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub bags: Vec<String>,
+    pub bags_file: Option<String>,
+    pub format: Format,
+    pub filter: Option<String>,
+    pub max: usize,
+    pub top: usize,
+    pub inputs: Vec<String>,
+}
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut report = false;
+        let mut infer = false;
+        let mut inference_options = false;
+        let mut options = Options {
+            mode: Mode::Bags,
+            bags: Vec::new(),
+            bags_file: None,
+            format: Format::Text,
+            filter: None,
+            max: 20,
+            top: 5,
+            inputs: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+            match arg.as_str() {
+                "--bag" => options.bags.push(value()?),
+                "--bags" => options.bags_file = Some(value()?),
+                "--report" => report = true,
+                "--filter" => options.filter = Some(value()?),
+                "--infer" => infer = true,
+                "--max" | "--top" => {
+                    let n = value()?;
+                    let n: usize = n.parse().map_err(|e| format!("\"{}\" is not a valid {}: {}", n, arg, e))?;
+                    match arg.as_str() {
+                        "--max" => options.max = n,
+                        _ if n == 0 => return Err(String::from("--top must be at least 1")),
+                        _ => options.top = n,
+                    }
+                    inference_options = true;
+                },
+                "--format" | "-f" => options.format = value()?.parse().map_err(|e| format!("{}", e))?,
+                flag if flag.starts_with('-') && flag != "-" => return Err(format!("Unknown option {}", flag)),
+                path => options.inputs.push(String::from(path)),
+            }
+        }
+        let bags = !options.bags.is_empty() || options.bags_file.is_some();
+        let modes = [
+            (bags, Mode::Bags),
+            (report, Mode::Report),
+            (options.filter.is_some(), Mode::Filter),
+            (infer, Mode::Infer),
+        ];
+        let mut chosen = modes.iter().filter(|(chosen, _)| *chosen).map(|(_, mode)| *mode);
+        options.mode = match (chosen.next(), chosen.next()) {
+            (Some(mode), None) => mode,
+            (None, _) => return Err(String::from("Expected --bag, --bags, --report, --filter or --infer")),
+            (Some(_), Some(_)) => {
+                return Err(String::from("Only one of --bag/--bags, --report, --filter and --infer can be used at once"))
+            },
+        };
+        if inference_options && options.mode != Mode::Infer {
+            return Err(String::from("--max and --top only apply to --infer"));
+        }
+        match (options.mode, options.format) {
+            (Mode::Report, Format::Csv) => return Err(String::from("--report can only be text or json")),
+            (Mode::Bags | Mode::Filter | Mode::Infer, Format::Json | Format::Csv) => {
+                return Err(String::from("--format only applies to --report"))
+            },
+            _ => (),
+        }
+        Ok(options)
+    }
+
+    pub fn bags(&self) -> Result<Vec<Bag>, String> {
+        let mut bags = Vec::new();
+        for spec in &self.bags {
+            bags.push(bag::parse_bag(spec).map_err(|e| e.diagnostic("--bag", &[spec.as_str()]))?);
+        }
+        if let Some(path) = &self.bags_file {
+            let source = Source::File(path.into());
+            let lines = source.read_lines().map_err(|e| format!("error: {}: {}\n", source, e))?;
+            let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+            bags.extend(bag::parse_bags(&lines_str[..]).map_err(|e| e.diagnostic(path, &lines_str[..]))?);
+        }
+        Ok(bags)
+    }
+}
+
+fn read_games(source: &Source) -> Result<Vec<Game>, String> {
+    let lines = source.read_lines().map_err(|e| format!("error: {}: {}\n", source, e))?;
+    let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+    parse_games(&lines_str[..]).map_err(|e| e.diagnostic(&source.to_string(), &lines_str[..]))
+}
+
+fn render(options: &Options, games: &[Game], bags: &[Bag], filter: Option<&Filter>) -> Result<String, String> {
+    let rendered = match options.mode {
+        Mode::Infer => {
+            let inference = infer::infer(games, options.max, options.top, infer::MAX_CANDIDATES)
+                .map_err(|e| format!("error: {}, so try a smaller --max\n", e))?;
+            let mut to_return = infer::render_inference(&inference);
+            if inference.most_likely().is_some_and(|c| c.bag.iter().any(|(_, n)| n >= options.max)) {
+                to_return.push_str("The most likely bag has as many of a colour as --max allows, so a larger --max may find a likelier one.\n");
+            }
+            to_return
+        },
+        Mode::Filter => filter
+            .map(|f| filter::matching_ids(games, f).iter().map(|id| format!("{}\n", id)).collect())
+            .unwrap_or_default(),
+        Mode::Bags => bag::render_evaluations(&bag::evaluate(games, bags)[..]),
+        Mode::Report => {
+            let reports = analytics::analyse_games(games);
+            let summary = analytics::summarise(&reports[..]);
+            match options.format {
+                Format::Json => analytics::render_json(&reports[..], &summary) + "\n",
+                _ => analytics::render_text(&reports[..], &summary),
+            }
+        },
+    };
+    Ok(rendered)
+}
+
+// Returns whether every input parsed.
+pub fn run(options: &Options) -> bool {
+    let bags = match options.bags() {
+        Ok(b) => b,
+        Err(e) => {
+            eprint!("{}", e);
+            return false
+        },
+    };
+    let filter = match options.filter.as_ref().map(|f| Filter::parse(f).map_err(|e| e.diagnostic("--filter", &[f]))) {
+        Some(Err(e)) => {
+            eprint!("{}", e);
+            return false
+        },
+        Some(Ok(f)) => Some(f),
+        None => None,
+    };
+    let sources = match input::expand_sources(&options.inputs[..]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false
+        },
+    };
+    let mut success = true;
+    for source in &sources {
+        match read_games(source).and_then(|games| render(options, &games[..], &bags[..], filter.as_ref())) {
+            Ok(rendered) => {
+                if sources.len() > 1 {
+                    println!("==> {} <==", source);
+                }
+                print!("{}", rendered);
+            },
+            Err(e) => {
+                eprint!("{}", e);
+                success = false;
+            },
+        }
+    }
+    success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options() {
+        let parse = |args: &[&str]| Options::parse(args.iter().map(|s| String::from(*s)));
+        let options = parse(&["--bag", "12 red", "--bag", "big = 20 red", "--bags", "bags.txt", "games.txt"]).unwrap();
+        assert_eq!(vec!["12 red", "big = 20 red"], options.bags);
+        assert_eq!(Some(String::from("bags.txt")), options.bags_file);
+        assert_eq!(vec!["games.txt"], options.inputs);
+        assert_eq!(Mode::Bags, options.mode);
+        assert!(parse(&["games.txt"]).is_err());
+
+        let options = parse(&["--report", "--format", "json"]).unwrap();
+        assert_eq!((Mode::Report, Format::Json), (options.mode, options.format));
+        assert!(parse(&["--report", "--format", "csv"]).is_err());
+        assert!(parse(&["--report", "--bag", "1 red"]).is_err());
+        assert!(parse(&["--bag", "1 red", "--format", "json"]).is_err());
+
+        let options = parse(&["--filter", "any(red > 10)", "games.txt"]).unwrap();
+        assert_eq!((Mode::Filter, Some(String::from("any(red > 10)"))), (options.mode, options.filter));
+        assert!(parse(&["--filter", "red > 1", "--report"]).is_err());
+
+        let options = parse(&["--infer", "--max", "30", "--top", "3"]).unwrap();
+        assert_eq!((Mode::Infer, 30, 3), (options.mode, options.max, options.top));
+        assert!(parse(&["--infer", "--top", "0"]).is_err());
+        assert!(parse(&["--report", "--max", "30"]).is_err());
+        assert!(parse(&["--bag"]).is_err());
+
+        let error = parse(&["--bag", "12 rad, x"]).unwrap().bags().unwrap_err();
+        assert!(error.starts_with("error: missing a colour\n --> --bag:1:10"));
+    }
+}
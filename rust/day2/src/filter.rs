@@ -0,0 +1,314 @@
+use aoc_common::{ parse, ParseError };
+
+use crate::{ part1::{ Game, Set }, part2::{ self, max_set_colours } };
+
+// Filters are either about a whole game or about one set in it. `any` and `all` go from
+// the first to the second.
+//
+//   filter     = or
+//   or         = and ("or" and)*
+//   and        = not ("and" not)*
+//   not        = "not" not | "(" filter ")" | quantifier | comparison
+//   quantifier = ("any" | "all") "(" filter ")"
+//   comparison = value ("<" | "<=" | ">" | ">=" | "==" | "!=") value
+//   value      = number | colour | "id" | "sets" | "power" | "total"
+//
+// In a game a colour is the most cubes of it shown at once, and in a set it's how many
+// were shown (0 if none were). `id`, `sets` and `power` are about games, `total` is the
+// number of cubes in a set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scope {
+    Game,
+    Set,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(usize),
+    Colour(String),
+    Id,
+    Sets,
+    Power,
+    Total,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+impl Comparison {
+    fn holds(&self, a: usize, b: usize) -> bool {
+        match self {
+            Comparison::Less => a < b,
+            Comparison::LessOrEqual => a <= b,
+            Comparison::Greater => a > b,
+            Comparison::GreaterOrEqual => a >= b,
+            Comparison::Equal => a == b,
+            Comparison::NotEqual => a != b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Compare(Value, Comparison, Value),
+    // Whether any or all of a game's sets match.
+    Any(Box<Filter>),
+    All(Box<Filter>),
+}
+
+enum Context<'a> {
+    // The game, its minimal bag and that bag's power.
+    Game(&'a Game, &'a Set, usize),
+    Set(&'a Set),
+}
+
+impl Filter {
+    pub fn parse(s: &str) -> Result<Filter, ParseError> {
+        let tokens = tokenise(s)?;
+        let mut parser = Parser { line: s, tokens, next: 0 };
+        let filter = parser.or(Scope::Game)?;
+        match parser.peek() {
+            None => Ok(filter),
+            Some(token) => Err(parse::expected(s, token, "\"and\", \"or\" or the end of the filter")),
+        }
+    }
+
+    // `palette` is the colours that count towards the power, usually `part2::palette` of every game.
+    pub fn matches(&self, game: &Game, palette: &[&str]) -> bool {
+        let bag = max_set_colours(&game.revelations[..]);
+        self.evaluate(&Context::Game(game, &bag, part2::power(&bag, palette)))
+    }
+
+    fn evaluate(&self, context: &Context) -> bool {
+        match self {
+            Filter::And(a, b) => a.evaluate(context) && b.evaluate(context),
+            Filter::Or(a, b) => a.evaluate(context) || b.evaluate(context),
+            Filter::Not(a) => !a.evaluate(context),
+            Filter::Compare(a, comparison, b) => comparison.holds(value(a, context), value(b, context)),
+            Filter::Any(a) => sets(context).iter().any(|s| a.evaluate(&Context::Set(s))),
+            Filter::All(a) => sets(context).iter().all(|s| a.evaluate(&Context::Set(s))),
+        }
+    }
+}
+
+fn sets<'a>(context: &Context<'a>) -> &'a [Set] {
+    match context {
+        Context::Game(game, _, _) => &game.revelations[..],
+        // The parser doesn't allow quantifiers inside quantifiers.
+        Context::Set(_) => &[],
+    }
+}
+
+fn value(v: &Value, context: &Context) -> usize {
+    match (v, context) {
+        (Value::Number(n), _) => *n,
+        (Value::Colour(colour), Context::Game(_, bag, _) | Context::Set(bag)) => bag.get(colour).unwrap_or(0),
+        (Value::Id, Context::Game(game, _, _)) => game.id,
+        (Value::Sets, Context::Game(game, _, _)) => game.revelations.len(),
+        (Value::Power, Context::Game(_, _, power)) => *power,
+        (Value::Total, Context::Set(set)) => set.iter().map(|(_, n)| n).sum(),
+        // The parser only allows each value in its own scope.
+        _ => 0,
+    }
+}
+
+pub fn matching_ids(games: &[Game], filter: &Filter) -> Vec<usize> {
+    let palette = part2::palette(games);
+    games.iter().filter(|g| filter.matches(g, &palette[..])).map(|g| g.id).collect()
+}
+
+// Tokens are subslices of the filter, so errors can point at them.
+fn tokenise(s: &str) -> Result<Vec<&str>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '(' | ')' => 1,
+            '<' | '>' | '=' | '!' if rest[1..].starts_with('=') => 2,
+            '<' | '>' => 1,
+            _ if c.is_ascii_digit() => rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len()),
+            _ if c.is_alphabetic() || c == '_' => {
+                rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len())
+            },
+            _ => return Err(parse::expected(s, &rest[..c.len_utf8()], "a value, comparison or bracket")),
+        };
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    line: &'a str,
+    tokens: Vec<&'a str>,
+    next: usize,
+}
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.next).copied()
+    }
+    fn take(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        match self.peek() {
+            Some(token) => {
+                self.next += 1;
+                Ok(token)
+            },
+            None => Err(parse::missing_after(self.line, &self.line[self.line.len()..], expected)),
+        }
+    }
+    fn expect(&mut self, wanted: &str) -> Result<(), ParseError> {
+        let token = self.take(&format!("\"{}\"", wanted))?;
+        match token == wanted {
+            true => Ok(()),
+            false => Err(parse::expected(self.line, token, &format!("\"{}\"", wanted))),
+        }
+    }
+
+    fn or(&mut self, scope: Scope) -> Result<Filter, ParseError> {
+        let mut filter = self.and(scope)?;
+        while self.peek() == Some("or") {
+            self.next += 1;
+            filter = Filter::Or(Box::new(filter), Box::new(self.and(scope)?));
+        }
+        Ok(filter)
+    }
+    fn and(&mut self, scope: Scope) -> Result<Filter, ParseError> {
+        let mut filter = self.not(scope)?;
+        while self.peek() == Some("and") {
+            self.next += 1;
+            filter = Filter::And(Box::new(filter), Box::new(self.not(scope)?));
+        }
+        Ok(filter)
+    }
+    fn not(&mut self, scope: Scope) -> Result<Filter, ParseError> {
+        match self.peek() {
+            Some("not") => {
+                self.next += 1;
+                Ok(Filter::Not(Box::new(self.not(scope)?)))
+            },
+            Some("(") => {
+                self.next += 1;
+                let filter = self.or(scope)?;
+                self.expect(")")?;
+                Ok(filter)
+            },
+            Some(quantifier @ ("any" | "all")) => {
+                if scope == Scope::Set {
+                    return Err(parse::invalid(self.line, quantifier, format!("\"{}\" can't be used inside a set", quantifier)));
+                }
+                self.next += 1;
+                self.expect("(")?;
+                let filter = Box::new(self.or(Scope::Set)?);
+                self.expect(")")?;
+                Ok(if quantifier == "any" { Filter::Any(filter) } else { Filter::All(filter) })
+            },
+            _ => {
+                let a = self.value(scope)?;
+                let token = self.take("a comparison")?;
+                let comparison = match token {
+                    "<" => Comparison::Less,
+                    "<=" => Comparison::LessOrEqual,
+                    ">" => Comparison::Greater,
+                    ">=" => Comparison::GreaterOrEqual,
+                    "==" => Comparison::Equal,
+                    "!=" => Comparison::NotEqual,
+                    _ => return Err(parse::expected(self.line, token, "a comparison")),
+                };
+                Ok(Filter::Compare(a, comparison, self.value(scope)?))
+            },
+        }
+    }
+    fn value(&mut self, scope: Scope) -> Result<Value, ParseError> {
+        let token = self.take("a value")?;
+        let value = match token {
+            "id" => Value::Id,
+            "sets" => Value::Sets,
+            "power" => Value::Power,
+            "total" => Value::Total,
+            "and" | "or" | "not" | "any" | "all" => return Err(parse::expected(self.line, token, "a value")),
+            _ if token.starts_with(|c: char| c.is_ascii_digit()) => Value::Number(parse::parse_number(self.line, token)?),
+            _ if token.starts_with(|c: char| c.is_alphabetic()) => Value::Colour(String::from(token)),
+            _ => return Err(parse::expected(self.line, token, "a value")),
+        };
+        match (&value, scope) {
+            (Value::Id | Value::Sets | Value::Power, Scope::Set) => {
+                Err(parse::invalid(self.line, token, format!("\"{}\" is about games, so it can't be used inside any or all", token)))
+            },
+            (Value::Total, Scope::Game) => {
+                Err(parse::invalid(self.line, token, String::from("\"total\" is about sets, so it needs to be inside any or all")))
+            },
+            _ => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    const EXAMPLE: [&str; 5] = [
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    ];
+
+    fn ids(filter: &str) -> Vec<usize> {
+        matching_ids(&parse_games(&EXAMPLE).unwrap()[..], &Filter::parse(filter).unwrap())
+    }
+
+    #[test]
+    fn filters() {
+        assert_eq!(vec![3, 4], ids("any(red > 10)"));
+        assert_eq!(vec![3], ids("any(red > 10 and blue < 10)"));
+        assert_eq!(vec![1, 2, 5], ids("red <= 12 and green <= 13 and blue <= 14"));
+        assert_eq!(vec![1, 2, 5], ids("not (red > 12 or green > 13 or blue > 14)"));
+        assert_eq!(vec![1, 2], ids("all(total < 10)"));
+        assert_eq!(vec![1, 5], ids("power < 50 and id != 2"));
+        assert_eq!(vec![2, 3, 4, 5], ids("any(total >= 10) or sets > 3 or id == 2 and power > 0"));
+        // Colours nobody drew count as 0.
+        assert_eq!(vec![1, 2, 3, 4, 5], ids("yellow == 0"));
+
+        let games = parse_games(&["Game 1: 2 yellow, 1 purple; 3 yellow", "Game 2: 4 purple, 5 yellow"]).unwrap();
+        assert_eq!(vec![2], matching_ids(&games[..], &Filter::parse("power > 3").unwrap()));
+    }
+
+    #[test]
+    fn precedence() {
+        let filter = Filter::parse("id == 1 or id == 2 and id == 3").unwrap();
+        assert!(matches!(filter, Filter::Or(_, _)));
+        assert_eq!(vec![1], ids("id == 1 or id == 2 and id == 3"));
+        assert_eq!(Vec::<usize>::new(), ids("(id == 1 or id == 2) and id == 3"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = Filter::parse("any(red > 10 and id > 2)").unwrap_err();
+        assert_eq!((18, 2), (error.column, error.len));
+        let error = Filter::parse("total > 10").unwrap_err();
+        assert_eq!(1, error.column);
+        let error = Filter::parse("any(all(red > 1))").unwrap_err();
+        assert_eq!(5, error.column);
+        let error = Filter::parse("red >").unwrap_err();
+        assert_eq!(6, error.column);
+        let error = Filter::parse("red = 3").unwrap_err();
+        assert_eq!(5, error.column);
+        let error = Filter::parse("(red > 3").unwrap_err();
+        assert_eq!(9, error.column);
+        let error = Filter::parse("red > 3 blue").unwrap_err();
+        assert_eq!(9, error.column);
+        let error = Filter::parse("red > 99999999999999999999").unwrap_err();
+        assert_eq!(7, error.column);
+    }
+}
@@ -0,0 +1,62 @@
+use aoc_common::Rng;
+
+use crate::part1::{ Game, Set };
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+// Counts go a little past the bag in part 1, so some games are impossible.
+pub fn game(rng: &mut Rng, id: usize) -> Game {
+    let revelations: Vec<Set> = (0..rng.range(1..7))
+        .map(|_| {
+            let mut colours = COLOURS;
+            rng.shuffle(&mut colours[..]);
+            let counts: Vec<(&str, usize)> = colours[..rng.range(1..4)]
+                .iter()
+                .map(|colour| (*colour, rng.range(1..21)))
+                .collect();
+            Set::from_counts(&counts[..])
+        })
+        .collect();
+    Game { id, revelations }
+}
+
+pub fn game_line(rng: &mut Rng, id: usize) -> String {
+    game(rng, id).to_string()
+}
+
+pub fn generate(rng: &mut Rng, games: usize) -> Vec<String> {
+    (1..=games.max(1)).map(|id| game_line(rng, id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ parse_games, solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let games = 1 + seed as usize * 2;
+            let lines = generate(&mut Rng::new(seed), games);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert_eq!(games, parse_games(&lines_str[..]).unwrap().len());
+            assert!(solve_part1(&lines_str[..]).unwrap() <= games * (games + 1) / 2);
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn generated_games_round_trip() {
+        let mut rng = Rng::new(3);
+        for id in 1..200 {
+            let game = game(&mut rng, id);
+            assert_eq!(game, crate::parse_game(&game.to_string()).unwrap());
+            assert_eq!(game, crate::parse_game(&crate::part1::canonical(&game)).unwrap());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
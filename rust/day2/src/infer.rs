@@ -0,0 +1,310 @@
+use std::fmt::Display;
+
+use crate::{ part1::{ Game, Set }, part2::max_set_colours };
+
+// How many bags `infer` will try, which takes about a second in a release build.
+pub const MAX_CANDIDATES: usize = 10_000_000;
+
+// Estimates what's really in the bag, assuming each set was drawn from it without
+// replacement and the cubes were put back before the next set. The chance of a set is then
+// hypergeometric: the ways of picking each colour's count from that colour's cubes, over
+// the ways of picking that many cubes from the whole bag.
+
+// ln(n!) for every n up to the biggest bag needed.
+struct LogFactorials(Vec<f64>);
+impl LogFactorials {
+    fn up_to(n: usize) -> LogFactorials {
+        let mut table = vec![0.0; n + 1];
+        for i in 1..=n {
+            table[i] = table[i - 1] + (i as f64).ln();
+        }
+        LogFactorials(table)
+    }
+    fn ln_choose(&self, n: usize, k: usize) -> f64 {
+        match k > n {
+            true => f64::NEG_INFINITY,
+            false => self.0[n] - self.0[k] - self.0[n - k],
+        }
+    }
+}
+
+// Each set as counts of `colours`, in that order.
+struct Observations {
+    colours: Vec<String>,
+    sets: Vec<Vec<usize>>,
+}
+impl Observations {
+    fn new(games: &[Game]) -> Observations {
+        let mut colours: Vec<String> = Vec::new();
+        for set in games.iter().flat_map(|g| g.revelations.iter()) {
+            for colour in set.colours() {
+                if !colours.iter().any(|c| c == colour) {
+                    colours.push(String::from(colour));
+                }
+            }
+        }
+        let sets = games
+            .iter()
+            .flat_map(|g| g.revelations.iter())
+            .map(|set| colours.iter().map(|c| set.get(c).unwrap_or(0)).collect())
+            .collect();
+        Observations { colours, sets }
+    }
+    fn log_likelihood(&self, counts: &[usize], table: &LogFactorials) -> f64 {
+        let total: usize = counts.iter().sum();
+        self.sets
+            .iter()
+            .map(|drawn| {
+                let ways: f64 = counts.iter().zip(drawn).map(|(n, k)| table.ln_choose(*n, *k)).sum();
+                match ways == f64::NEG_INFINITY {
+                    true => ways,
+                    false => ways - table.ln_choose(total, drawn.iter().sum()),
+                }
+            })
+            .sum()
+    }
+    fn counts(&self, bag: &Set) -> Vec<usize> {
+        self.colours.iter().map(|c| bag.get(c).unwrap_or(0)).collect()
+    }
+    fn set(&self, counts: &[usize]) -> Set {
+        let counts: Vec<(&str, usize)> = self.colours.iter().map(|c| c.as_str()).zip(counts.iter().copied()).collect();
+        Set::from_counts(&counts[..])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub bag: Set,
+    // Negative infinity if the bag couldn't have given the games.
+    pub log_likelihood: f64,
+    // The share of the likelihood of every candidate, as if each was as likely beforehand.
+    pub probability: f64,
+}
+
+// Keeps the best `top` candidates while adding up the likelihood of all of them.
+struct Ranking {
+    top: usize,
+    best: Vec<(Set, f64)>,
+    max: f64,
+    // The sum of exp(log_likelihood - max) over every candidate.
+    scaled_total: f64,
+    candidates: usize,
+}
+impl Ranking {
+    fn new(top: usize) -> Ranking {
+        Ranking { top, best: Vec::new(), max: f64::NEG_INFINITY, scaled_total: 0.0, candidates: 0 }
+    }
+    fn push(&mut self, bag: impl FnOnce() -> Set, log_likelihood: f64) {
+        self.candidates += 1;
+        if log_likelihood > self.max {
+            self.scaled_total = self.scaled_total * (self.max - log_likelihood).exp() + 1.0;
+            self.max = log_likelihood;
+        }
+        else if log_likelihood > f64::NEG_INFINITY {
+            self.scaled_total += (log_likelihood - self.max).exp();
+        }
+        let worst = self.best.last().map(|(_, l)| *l);
+        if self.best.len() < self.top || worst.is_some_and(|w| log_likelihood > w) {
+            let i = self.best.partition_point(|(_, l)| *l >= log_likelihood);
+            self.best.insert(i, (bag(), log_likelihood));
+            self.best.truncate(self.top);
+        }
+    }
+    fn finish(self) -> Inference {
+        let ranked = self
+            .best
+            .into_iter()
+            .map(|(bag, log_likelihood)| Candidate {
+                bag,
+                log_likelihood,
+                probability: match self.scaled_total > 0.0 {
+                    true => (log_likelihood - self.max).exp() / self.scaled_total,
+                    false => 0.0,
+                },
+            })
+            .collect();
+        Inference { ranked, candidates: self.candidates }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference {
+    // The most likely candidates first.
+    pub ranked: Vec<Candidate>,
+    // How many bags were considered.
+    pub candidates: usize,
+}
+impl Inference {
+    pub fn most_likely(&self) -> Option<&Candidate> {
+        self.ranked.first()
+    }
+}
+
+pub fn log_likelihood(bag: &Set, games: &[Game]) -> f64 {
+    let observations = Observations::new(games);
+    let counts = observations.counts(bag);
+    observations.log_likelihood(&counts[..], &LogFactorials::up_to(counts.iter().sum()))
+}
+
+// Ranks the given bags, keeping the best `top`.
+pub fn rank(candidates: &[Set], games: &[Game], top: usize) -> Inference {
+    let observations = Observations::new(games);
+    let counts: Vec<Vec<usize>> = candidates.iter().map(|bag| observations.counts(bag)).collect();
+    let table = LogFactorials::up_to(counts.iter().map(|c| c.iter().sum()).max().unwrap_or(0));
+    let mut ranking = Ranking::new(top);
+    for (bag, counts) in candidates.iter().zip(counts) {
+        ranking.push(|| bag.clone(), observations.log_likelihood(&counts[..], &table));
+    }
+    ranking.finish()
+}
+
+// There are too many bags to try them all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TooManyBags {
+    // None if there are more than fit in a usize.
+    pub bags: Option<usize>,
+    pub limit: usize,
+}
+impl Display for TooManyBags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bags {
+            Some(bags) => write!(f, "there are {} bags to try, more than the limit of {}", bags, self.limit),
+            None => write!(f, "there are too many bags to count, let alone try, with a limit of {}", self.limit),
+        }
+    }
+}
+impl std::error::Error for TooManyBags {}
+
+// Tries every bag with at least as many of each colour as was ever shown at once, and at most
+// `max` of each (or the most shown, if that's more). Colours that were never shown aren't in the bag.
+// Nothing is tried if that's more than `limit` bags.
+pub fn infer(games: &[Game], max: usize, top: usize, limit: usize) -> Result<Inference, TooManyBags> {
+    let observations = Observations::new(games);
+    let revelations: Vec<Set> = games.iter().flat_map(|g| g.revelations.iter().cloned()).collect();
+    let lows = observations.counts(&max_set_colours(&revelations[..]));
+    let highs: Vec<usize> = lows.iter().map(|low| max.max(*low)).collect();
+    let bags = lows.iter().zip(&highs).try_fold(1_usize, |bags, (low, high)| bags.checked_mul(high - low + 1));
+    if bags.is_none_or(|bags| bags > limit) {
+        return Err(TooManyBags { bags, limit });
+    }
+    let table = LogFactorials::up_to(highs.iter().sum());
+
+    let mut ranking = Ranking::new(top);
+    let mut counts = lows.clone();
+    loop {
+        ranking.push(|| observations.set(&counts[..]), observations.log_likelihood(&counts[..], &table));
+        // Counts go up like an odometer, with the first colour turning fastest.
+        let Some(i) = (0..counts.len()).find(|i| counts[*i] < highs[*i]) else {
+            break;
+        };
+        counts[i] += 1;
+        counts[..i].copy_from_slice(&lows[..i]);
+    }
+    Ok(ranking.finish())
+}
+
+pub fn render_inference(inference: &Inference) -> String {
+    let mut to_return = String::new();
+    match inference.most_likely() {
+        Some(best) => to_return.push_str(&format!(
+            "Most likely bag: {} (log-likelihood {:.3}, {} considered)\n",
+            best.bag,
+            best.log_likelihood,
+            match inference.candidates {
+                1 => String::from("1 bag"),
+                n => format!("{} bags", n),
+            },
+        )),
+        None => return String::from("No bags to consider\n"),
+    }
+    to_return.push_str("Rank  Probability  Log-likelihood  Bag\n");
+    for (i, candidate) in inference.ranked.iter().enumerate() {
+        to_return.push_str(&format!(
+            "{:>4}  {:>10.2}%  {:>14.3}  {}\n",
+            i + 1,
+            candidate.probability * 100.0,
+            candidate.log_likelihood,
+            candidate.bag,
+        ));
+    }
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Rng;
+    use crate::parse_games;
+
+    #[test]
+    fn likelihood() {
+        let games = parse_games(&["Game 1: 2 red, 1 blue; 1 red, 1 blue"]).unwrap();
+        // The first set takes the whole bag, and the second has 2 ways out of 3.
+        let bag = Set::from_counts(&[("red", 2), ("blue", 1)]);
+        assert!((log_likelihood(&bag, &games[..]) - (2.0_f64 / 3.0).ln()).abs() < 1e-9);
+        let too_small = Set::from_counts(&[("red", 1), ("blue", 1)]);
+        assert_eq!(f64::NEG_INFINITY, log_likelihood(&too_small, &games[..]));
+    }
+
+    #[test]
+    fn ranks_candidates() {
+        let games = parse_games(&["Game 1: 2 red, 1 blue; 1 red, 1 blue"]).unwrap();
+        let candidates = [
+            Set::from_counts(&[("red", 1), ("blue", 1)]),
+            Set::from_counts(&[("red", 2), ("blue", 1)]),
+            Set::from_counts(&[("red", 3), ("blue", 3)]),
+        ];
+        let inference = rank(&candidates, &games[..], 3);
+        assert_eq!(candidates[1], inference.ranked[0].bag);
+        assert_eq!(candidates[0], inference.ranked[2].bag);
+        assert_eq!(0.0, inference.ranked[2].probability);
+        let total: f64 = inference.ranked.iter().map(|c| c.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finds_the_bag() {
+        // Draw sets of 4 from a bag of 6 red, 3 green and 1 blue.
+        let mut rng = Rng::new(11);
+        let mut bag: Vec<&str> = [["red"; 6].as_slice(), &["green"; 3], &["blue"]].concat();
+        let mut sets: Vec<String> = Vec::new();
+        for _ in 0..200 {
+            rng.shuffle(&mut bag[..]);
+            let mut set = Set::new();
+            for colour in &bag[..4] {
+                set.insert(colour, set.get(colour).unwrap_or(0) + 1);
+            }
+            sets.push(set.to_string());
+        }
+        let line = format!("Game 1: {}", sets.join("; "));
+        let games = parse_games(&[line.as_str()]).unwrap();
+
+        let inference = infer(&games[..], 12, 5, MAX_CANDIDATES).unwrap();
+        assert_eq!(5, inference.ranked.len());
+        assert!(inference.ranked.windows(2).all(|w| w[0].log_likelihood >= w[1].log_likelihood));
+        let best = &inference.most_likely().unwrap().bag;
+        assert_eq!(Some(1), best.get("blue"));
+        assert!(best.get("red") > best.get("green"));
+
+        let all = infer(&games[..], 12, usize::MAX, MAX_CANDIDATES).unwrap();
+        assert_eq!(all.candidates, all.ranked.len());
+        let total: f64 = all.ranked.iter().map(|c| c.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(inference.ranked[..], all.ranked[..5]);
+    }
+
+    #[test]
+    fn limits_the_search() {
+        // 1 to 4 red and 2 to 4 blue.
+        let games = parse_games(&["Game 1: 1 red, 2 blue"]).unwrap();
+        assert_eq!(12, infer(&games[..], 4, 1, 12).unwrap().candidates);
+        assert_eq!(Err(TooManyBags { bags: Some(12), limit: 11 }), infer(&games[..], 4, 1, 11));
+
+        let colours = ["red", "green", "blue", "yellow", "purple", "orange", "white", "black"];
+        let set: Vec<String> = colours.iter().map(|c| format!("1 {}", c)).collect();
+        let line = format!("Game 1: {}", set.join(", "));
+        let games = parse_games(&[line.as_str()]).unwrap();
+        assert_eq!(Some(20_usize.pow(8)), infer(&games[..], 20, 5, MAX_CANDIDATES).unwrap_err().bags);
+        assert_eq!(None, infer(&games[..], usize::MAX, 5, MAX_CANDIDATES).unwrap_err().bags);
+    }
+}
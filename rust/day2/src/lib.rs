@@ -0,0 +1,50 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod analytics;
+pub mod bag;
+pub mod cli;
+pub mod filter;
+pub mod generate;
+pub mod infer;
+pub mod part1;
+pub mod part2;
+
+pub use bag::Bag;
+pub use part1::{ canonical, is_game_possible, is_game_possible_with, parse_game, parse_games, Game, Set, PALETTE };
+pub use part2::{ max_set_colours, order_of_set, palette, power };
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    solve_part1_with(input, &part1::default_bag())
+}
+
+pub fn solve_part1_with(input: &[&str], bag: &Set) -> Result<usize, ParseError> {
+    Ok(parse_games(input)?
+        .iter()
+        .filter(|g| is_game_possible_with(g, bag))
+        .map(|g| g.id)
+        .sum())
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    Ok(part2::sum_min_power_of_max_sets(&parse_games(input)?[..]))
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        parse_games(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
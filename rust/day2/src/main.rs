@@ -1,27 +1,24 @@
-mod part1;
-mod part2;
+use day2::cli::{ self, Options };
 
-fn main() -> std::io::Result<()> {
-    let lines: Vec<String> = std::io::stdin()
-        .lines()
-        .collect::<Result<_, _>>()?;
-    if lines.is_empty() {
-        eprintln!("Nothing found in stdin!");
-        std::process::exit(1);
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}\n\n{}", aoc_common::cli::DAY_USAGE, cli::USAGE);
+        return
     }
-    let mut games: Vec<part1::Game> = Vec::new();
-    for l in lines {
-        games.push(part1::parse_game(&l));
+    let modes = ["--bag", "--bags", "--report", "--filter", "--infer"];
+    if !args.iter().any(|a| modes.contains(&a.as_str())) {
+        aoc_common::cli::run_day(&day2::Day2);
+        return
+    }
+    let options = match Options::parse(args.into_iter()) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        },
+    };
+    if !cli::run(&options) {
+        std::process::exit(1);
     }
-    let valid_games_id_sum: usize = games
-        .iter()
-        .filter(|g| part1::is_game_possible(g))
-        .map(|g| g.id)
-        .sum();
-
-    println!("{}", valid_games_id_sum);
-
-    let sum = part2::sum_min_power_of_max_sets(&games[..]);
-    println!("{}", sum);
-    Ok(())
 }
@@ -1,17 +1,59 @@
+use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use aoc_common::{ parse, ParseError, VecMap };
+
+// The colours the puzzle uses.
+pub const PALETTE: [&str; 3] = ["red", "green", "blue"];
+
+// How many cubes of each colour were seen. A colour that wasn't seen has no entry.
+#[derive(Debug, Clone, Default)]
 pub struct Set {
-    pub red: Option<usize>,
-    pub green: Option<usize>,
-    pub blue: Option<usize>,
+    pub cubes: VecMap<String, usize>,
 }
 impl Set {
     pub fn new() -> Set {
-        Set {
-            red: None,
-            green: None,
-            blue: None,
+        Set { cubes: VecMap::new() }
+    }
+    pub fn from_counts(counts: &[(&str, usize)]) -> Set {
+        let mut set = Set::new();
+        for (colour, count) in counts {
+            set.insert(colour, *count);
+        }
+        set
+    }
+    pub fn get(&self, colour: &str) -> Option<usize> {
+        self.cubes.vec.iter().find(|(c, _)| c == colour).map(|(_, n)| *n)
+    }
+    pub fn insert(&mut self, colour: &str, count: usize) {
+        self.cubes.update((String::from(colour), count));
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.cubes.vec.iter().map(|(c, n)| (c.as_str(), *n))
+    }
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(c, _)| c)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cubes.vec.is_empty()
+    }
+}
+// The order colours were listed in doesn't matter.
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.cubes.vec.len() == other.cubes.vec.len() && self.iter().all(|(c, n)| other.get(c) == Some(n))
+    }
+}
+impl Eq for Set {}
+// Writes the counts in the order they were given, like "3 blue, 4 red".
+impl Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (colour, count)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, colour)?;
         }
+        Ok(())
     }
 }
 
@@ -21,61 +63,112 @@ pub struct Game {
     // "revelations" because these are the sets which are "revealed".
     pub revelations: Vec<Set>,
 }
+impl Game {
+    // Whether the game could have come from `parse_game`: at least one set, no empty sets, and
+    // colours that are single words.
+    pub fn is_well_formed(&self) -> bool {
+        !self.revelations.is_empty() && self.revelations.iter().all(|s| !s.is_empty() && s.colours().all(is_colour))
+    }
+}
+// Writes the game as it appears in the puzzle input. For a well formed game, `parse_game` gives
+// back an equal one. Anything else, like a colour of "light blue", writes text that doesn't parse.
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, set) in self.revelations.iter().enumerate() {
+            write!(f, "{} {}", if i > 0 { ";" } else { "" }, set)?;
+        }
+        Ok(())
+    }
+}
 
-const CONFIGURATION: Set = Set { red: Some(12), green: Some(13), blue: Some(14) };
+// The same set with the palette's colours first, in palette order, then any others alphabetically.
+pub fn canonical_set(set: &Set) -> Set {
+    let mut colours: Vec<(&str, usize)> = set.iter().collect();
+    colours.sort_by_key(|(colour, _)| (PALETTE.iter().position(|c| c == colour).unwrap_or(PALETTE.len()), *colour));
+    Set::from_counts(&colours[..])
+}
 
-pub fn parse_game(s: &str) -> Game {
-    let mut colon_split = s.split(':');
-    let game_half = colon_split.next().unwrap();
-    let revelations_half = colon_split.next().unwrap();
+// Formats games the same way whatever order their colours were listed in.
+pub fn canonical(game: &Game) -> String {
+    Game { id: game.id, revelations: game.revelations.iter().map(canonical_set).collect() }.to_string()
+}
 
-    let id: usize = (&game_half[5..(game_half.len())]).parse().unwrap();
+// A single word of letters, so it can't be mistaken for a count or a separator.
+pub fn is_colour(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_alphabetic())
+}
 
-    let mut revelations: Vec<Set> = Vec::new();
-    for revelation in revelations_half.split(';') {
-        let mut new_set = Set::new();
-        for colour_text in revelation.split(',') {
-            let mut colour_iter = colour_text.trim().split(' ');
-            let num = colour_iter.next().unwrap();
-            let colour = colour_iter.next().unwrap();
-            match colour {
-                "red" => new_set.red = Some(num.parse().unwrap()),
-                "green" => new_set.green = Some(num.parse().unwrap()),
-                "blue" => new_set.blue = Some(num.parse().unwrap()),
-                _ => unreachable!(),
-            }
+const CONFIGURATION: [(&str, usize); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+// The bag from the puzzle.
+pub fn default_bag() -> Set {
+    Set::from_counts(&CONFIGURATION)
+}
+
+// Parses counts like "3 blue, 4 red", where `text` is a subslice of `line`.
+pub fn parse_set(line: &str, text: &str) -> Result<Set, ParseError> {
+    let mut new_set = Set::new();
+    for colour_text in text.split(',') {
+        let colour_text = colour_text.trim();
+        let (num, colour) = match colour_text.split_once(' ') {
+            Some(tuple) => tuple,
+            None if colour_text.is_empty() => {
+                return Err(parse::missing_after(line, colour_text, "a number of cubes"))
+            },
+            None => return Err(parse::missing_after(line, colour_text, "a colour")),
+        };
+        let num: usize = parse::parse_number(line, num)?;
+        if !is_colour(colour) {
+            return Err(parse::expected(line, colour, "a colour"));
         }
-        revelations.push(new_set);
+        if new_set.get(colour).is_some() {
+            return Err(parse::invalid(line, colour, format!("{} is counted twice in the same set", colour)));
+        }
+        new_set.insert(colour, num);
     }
+    Ok(new_set)
+}
+
+pub fn parse_game(s: &str) -> Result<Game, ParseError> {
+    let (game_half, revelations_half) = parse::split_once(s, s, ':')?;
+
+    let id: usize = parse::parse_number(s, parse::expect_prefix(s, game_half, "Game ")?)?;
 
-    Game { id, revelations }
+    let revelations = revelations_half
+        .split(';')
+        .map(|revelation| parse_set(s, revelation))
+        .collect::<Result<Vec<Set>, ParseError>>()?;
+
+    Ok(Game { id, revelations })
+}
+
+pub fn parse_games(lines: &[&str]) -> Result<Vec<Game>, ParseError> {
+    parse::parse_lines(lines, parse_game)
 }
 
+// Colours missing from `b` count as none at all.
 pub fn is_game_subseteq_of(a: &Set, b: &Set) -> bool {
-    if a.red > b.red {
-        return false;
-    }
-    if a.green > b.green {
-        return false;
-    }
-    if a.blue > b.blue {
-        return false;
-    }
-    true
+    a.iter().all(|(colour, count)| count <= b.get(colour).unwrap_or(0))
 }
 
-pub fn is_game_possible(g: &Game) -> bool {
+pub fn is_game_possible_with(g: &Game, bag: &Set) -> bool {
     for r in &g.revelations {
-        if !is_game_subseteq_of(&r, &CONFIGURATION) {
+        if !is_game_subseteq_of(r, bag) {
             return false;
         }
     }
     true
 }
 
+pub fn is_game_possible(g: &Game) -> bool {
+    is_game_possible_with(g, &default_bag())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::ParseErrorKind;
 
     #[test]
     fn sanity_check() {
@@ -87,13 +180,13 @@ mod tests {
         let control_game = Game {
             id: 1,
             revelations: vec![
-                Set { red: Some(4), green: None, blue: Some(3) },
-                Set { red: Some(1), green: Some(2), blue: Some(6) },
-                Set { red: None, green: Some(2), blue: None },
+                Set::from_counts(&[("red", 4), ("blue", 3)]),
+                Set::from_counts(&[("red", 1), ("green", 2), ("blue", 6)]),
+                Set::from_counts(&[("green", 2)]),
             ],
         };
         let test_str = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        assert_eq!(parse_game(&test_str), control_game);
+        assert_eq!(parse_game(&test_str).unwrap(), control_game);
     }
 
     #[test]
@@ -107,9 +200,100 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
         );
         let mut games: Vec<Game> = Vec::new();
         for line in input.lines() {
-            games.push(parse_game(line));
+            games.push(parse_game(line).unwrap());
         }
         let id_sum: usize = games.iter().filter(|g| is_game_possible(g)).map(|g| g.id).sum();
         assert_eq!(8, id_sum);
     }
+    #[test]
+    fn parse_errors() {
+        let error = parse_game("Game 1: 3 blue, 4 r3d").unwrap_err();
+        assert_eq!(ParseError::new(ParseErrorKind::Expected {
+            expected: String::from("a colour"),
+            found: String::from("r3d"),
+        }, 19, 3), error);
+
+        let error = parse_game("Game 1: 3 blue, 4 blue").unwrap_err();
+        assert_eq!((19, 4), (error.column, error.len));
+
+        let error = parse_game("Gmae 1: 3 blue").unwrap_err();
+        assert_eq!((1, 5), (error.column, error.len));
+
+        let error = parse_game("Game 1: 3 blue; x red").unwrap_err();
+        assert_eq!(17, error.column);
+
+        let error = parse_game("Game 1 3 blue").unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Missing { .. }));
+
+        let error = parse_game("Game 1: 3 blue,").unwrap_err();
+        assert_eq!(16, error.column);
+    }
+
+    #[test]
+    fn any_colours() {
+        let game = parse_game("Game 7: 2 yellow, 1 purple; 3 red").unwrap();
+        assert_eq!(Some(2), game.revelations[0].get("yellow"));
+        assert_eq!(vec!["yellow", "purple"], game.revelations[0].colours().collect::<Vec<&str>>());
+        assert!(!is_game_possible(&game));
+        assert!(is_game_possible(&parse_game("Game 8: 12 red, 14 blue").unwrap()));
+
+        let bag = Set::from_counts(&[("yellow", 2), ("purple", 1), ("red", 3)]);
+        assert!(game.revelations.iter().all(|r| is_game_subseteq_of(r, &bag)));
+    }
+
+    #[test]
+    fn order_doesnt_matter() {
+        assert_eq!(Set::from_counts(&[("red", 1), ("blue", 2)]), Set::from_counts(&[("blue", 2), ("red", 1)]));
+        assert_ne!(Set::from_counts(&[("red", 1)]), Set::from_counts(&[("red", 1), ("blue", 2)]));
+        assert_ne!(Set::from_counts(&[("red", 1), ("blue", 2)]), Set::from_counts(&[("red", 1)]));
+    }
+
+    #[test]
+    fn round_trip() {
+        let lines = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 12: 1 yellow",
+            "Game 3: 8 green, 6 blue, 20 red; 2 purple, 5 blue",
+        ];
+        for line in lines {
+            let game = parse_game(line).unwrap();
+            assert_eq!(line, game.to_string());
+            assert_eq!(game, parse_game(&canonical(&game)).unwrap());
+        }
+        // Extra spaces aren't kept.
+        assert_eq!("Game 2: 3 blue; 4 red", parse_game("Game 2:  3 blue ;4 red").unwrap().to_string());
+    }
+
+    #[test]
+    fn round_trip_built_games() {
+        let games = [
+            Game { id: 7, revelations: vec![Set::from_counts(&[("blue", 0)])] },
+            Game { id: 8, revelations: vec![Set::from_counts(&[("violet", 2), ("red", 1)]), Set::from_counts(&[("ochre", 30)])] },
+        ];
+        for game in games {
+            assert!(game.is_well_formed());
+            assert_eq!(game, parse_game(&game.to_string()).unwrap());
+            assert_eq!(game, parse_game(&canonical(&game)).unwrap());
+        }
+
+        let malformed = [
+            Game { id: 1, revelations: vec![] },
+            Game { id: 2, revelations: vec![Set::from_counts(&[("red", 1)]), Set::new()] },
+            Game { id: 3, revelations: vec![Set::from_counts(&[("light blue", 1)])] },
+            Game { id: 4, revelations: vec![Set::from_counts(&[("red,", 1)])] },
+            Game { id: 5, revelations: vec![Set::from_counts(&[("red;", 1)])] },
+            Game { id: 6, revelations: vec![Set::from_counts(&[("r3d", 1)])] },
+        ];
+        for game in malformed {
+            assert!(!game.is_well_formed());
+            assert!(parse_game(&game.to_string()).is_err(), "{} parsed", game);
+        }
+    }
+
+    #[test]
+    fn canonical_order() {
+        let game = parse_game("Game 3: 2 purple, 5 blue, 1 amber, 8 red; 2 green").unwrap();
+        assert_eq!("Game 3: 8 red, 5 blue, 1 amber, 2 purple; 2 green", canonical(&game));
+        assert_eq!(canonical(&game), canonical(&parse_game(&canonical(&game)).unwrap()));
+    }
 }
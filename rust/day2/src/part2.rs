@@ -1,39 +1,43 @@
 use crate::part1::{ Game, Set };
 
-pub fn order_of_set(s: &Set) -> Option<usize> {
-    Some(s.red? * s.green? * s.blue?)
+// The product of the counts of every colour in the palette, or None if any of them is missing.
+pub fn order_of_set(s: &Set, palette: &[&str]) -> Option<usize> {
+    palette.iter().map(|colour| s.get(colour)).product()
 }
 
+// The fewest cubes of each colour that could have given every set.
 pub fn max_set_colours(sets: &[Set]) -> Set {
-    let max_red = sets
-        .iter()
-        .filter(|s| s.red.is_some())
-        .map(|s| s.red.unwrap())
-        .max();
-
-    let max_green = sets
-        .iter()
-        .filter(|s| s.green.is_some())
-        .map(|s| s.green.unwrap())
-        .max();
+    let mut to_return = Set::new();
+    for set in sets {
+        for (colour, count) in set.iter() {
+            to_return.cubes.update_with_fn(String::from(colour), count, |n| count.max(*n));
+        }
+    }
+    to_return
+}
 
-    let max_blue = sets
-        .iter()
-        .filter(|s| s.blue.is_some())
-        .map(|s| s.blue.unwrap())
-        .max();
+// Every colour any of the games shows, in the order they're first shown. For the puzzle's
+// input that's red, green and blue in some order.
+pub fn palette(games: &[Game]) -> Vec<&str> {
+    let mut to_return: Vec<&str> = Vec::new();
+    for colour in games.iter().flat_map(|g| g.revelations.iter()).flat_map(|s| s.colours()) {
+        if !to_return.contains(&colour) {
+            to_return.push(colour);
+        }
+    }
+    to_return
+}
 
-    Set { red: max_red, green: max_green, blue: max_blue }
+// A colour in the palette that's never shown needs no cubes at all, so the power is 0.
+pub fn power(bag: &Set, palette: &[&str]) -> usize {
+    order_of_set(bag, palette).unwrap_or(0)
 }
 
 pub fn sum_min_power_of_max_sets(games: &[Game]) -> usize {
+    let palette = palette(games);
     games
         .iter()
-        .map(|g|
-            order_of_set(
-                &max_set_colours(&g.revelations[..])
-            ).unwrap()
-        )
+        .map(|g| power(&max_set_colours(&g.revelations[..]), &palette[..]))
         .sum()
 }
 
@@ -53,9 +57,30 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
         );
         let mut games: Vec<Game> = Vec::new();
         for line in input.lines() {
-            games.push(crate::part1::parse_game(line));
+            games.push(crate::part1::parse_game(line).unwrap());
         }
         let sum = sum_min_power_of_max_sets(&games[..]);
         assert_eq!(2286, sum);
     }
+
+    #[test]
+    fn missing_colour() {
+        let games = crate::part1::parse_games(&["Game 1: 3 blue, 4 red; 1 red, 6 blue", "Game 2: 1 green, 2 blue, 3 red"]).unwrap();
+        assert_eq!(6, sum_min_power_of_max_sets(&games[..]));
+        // Green isn't in the palette without the second game.
+        assert_eq!(24, sum_min_power_of_max_sets(&games[..1]));
+    }
+
+    #[test]
+    fn other_palettes() {
+        let game = crate::part1::parse_game("Game 1: 2 yellow, 1 purple; 3 yellow, 4 red").unwrap();
+        let max = max_set_colours(&game.revelations[..]);
+        assert_eq!(Set::from_counts(&[("yellow", 3), ("purple", 1), ("red", 4)]), max);
+        assert_eq!(Some(12), order_of_set(&max, &["yellow", "red"]));
+        assert_eq!(None, order_of_set(&max, &crate::PALETTE));
+
+        let games = crate::part1::parse_games(&["Game 1: 2 yellow, 1 purple; 3 yellow", "Game 2: 4 purple, 5 yellow"]).unwrap();
+        assert_eq!(vec!["yellow", "purple"], palette(&games[..]));
+        assert_eq!(3 + 20, sum_min_power_of_max_sets(&games[..]));
+    }
 }
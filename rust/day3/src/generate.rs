@@ -0,0 +1,56 @@
+use aoc_common::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+// One row of a schematic: numbers of up to three digits, always followed by
+// something that isn't a digit so they never run into each other, and symbols.
+fn row(rng: &mut Rng, width: usize) -> String {
+    let mut to_return = String::new();
+    while to_return.len() < width {
+        let space_left = width - to_return.len();
+        if rng.chance(0.2) {
+            let digits = rng.range(1..4).min(space_left);
+            to_return.push_str(&rng.range(10_usize.pow(digits as u32 - 1)..10_usize.pow(digits as u32)).to_string());
+            if to_return.len() < width {
+                to_return.push('.');
+            }
+        }
+        else if rng.chance(0.1) {
+            to_return.push(*rng.choose(&SYMBOLS[..]));
+        }
+        else {
+            to_return.push('.');
+        }
+    }
+    to_return
+}
+
+// A square schematic `size` characters wide.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    (0..size).map(|_| row(rng, size)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let size = 1 + seed as usize;
+            let lines = generate(&mut Rng::new(seed), size);
+            assert_eq!(size, lines.len());
+            assert!(lines.iter().all(|l| l.len() == size));
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            solve_part1(&lines_str[..]).unwrap();
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
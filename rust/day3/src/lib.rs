@@ -0,0 +1,68 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+pub mod rules;
+pub mod schematic;
+
+pub use part1::{ find_part_numbers, parse_schematic, part_numbers };
+pub use part2::gear_ratios;
+pub use rules::{ Combine, SchematicRules };
+pub use schematic::{ NumberSpan, Schematic };
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    solve_part1_with(input, SchematicRules::default())
+}
+
+pub fn solve_part1_with(input: &[&str], rules: SchematicRules) -> Result<usize, ParseError> {
+    Ok(part_numbers(&Schematic::with_rules(input, rules)?).iter().map(|n| n.value).sum())
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    solve_part2_with(input, SchematicRules::default())
+}
+
+pub fn solve_part2_with(input: &[&str], rules: SchematicRules) -> Result<usize, ParseError> {
+    Ok(gear_ratios(&Schematic::with_rules(input, rules)?).iter().sum())
+}
+
+pub struct Day3;
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        Day3WithRules(SchematicRules::default()).parse(input)
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Day3WithRules(SchematicRules::default()).part1(input)
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Day3WithRules(SchematicRules::default()).part2(input)
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
+
+// Day 3 with rules other than the puzzle's.
+pub struct Day3WithRules(pub SchematicRules);
+impl Solution for Day3WithRules {
+    fn day(&self) -> u8 {
+        3
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        Schematic::with_rules(input, self.0.clone())?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1_with(input, self.0.clone())?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2_with(input, self.0.clone())?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
@@ -1,28 +1,35 @@
-mod part1;
-mod part2;
+use aoc_common::{ cli, input, Source };
+use day3::{ Day3WithRules, SchematicRules };
 
-fn main() -> std::io::Result<()> {
-    let lines: Vec<String> = std::io::stdin()
-        .lines()
-        .collect::<Result<_, _>>()?;
-    if lines.is_empty() {
-        eprintln!("Nothing found in stdin!");
-        std::process::exit(1);
-    }
-    let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
-    {
-        let mut grid = part1::Grid::from_lines(&lines_str[..]);
-        let part_numbers: Vec<usize> = grid.get_part_numbers();
-        let sum = part_numbers.iter().sum::<usize>();
-        println!("{}", sum);
-    }
+const RULES_USAGE: &str = "  --rules <FILE>   Which characters are blank, symbols and gears, and how gear ratios are worked out.";
 
-    {
-        let mut grid = part1::Grid::from_lines(&lines_str[..]);
-        let gear_ratios: Vec<usize> = part2::get_gear_ratios_and_delete(&mut grid);
-        let sum = gear_ratios.iter().sum::<usize>();
-        println!("{}", sum);
-    }
+fn read_rules(path: &str) -> Result<SchematicRules, String> {
+    let source = Source::File(path.into());
+    let lines = source.read_lines().map_err(|e| format!("error: {}: {}\n", source, e))?;
+    let lines_str: Vec<&str> = input::as_strs(&lines[..]);
+    SchematicRules::from_lines(&lines_str[..]).map_err(|e| e.diagnostic(path, &lines_str[..]))
+}
 
-    Ok(())
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}\n\n{}", cli::DAY_USAGE, RULES_USAGE);
+        return
+    }
+    let mut rules = SchematicRules::default();
+    if let Some(i) = args.iter().position(|a| a == "--rules") {
+        let Some(path) = args.get(i + 1).cloned() else {
+            eprintln!("--rules needs a value\n\n{}", RULES_USAGE);
+            std::process::exit(2);
+        };
+        rules = match read_rules(&path) {
+            Ok(r) => r,
+            Err(e) => {
+                eprint!("{}", e);
+                std::process::exit(2);
+            },
+        };
+        args.drain(i..i + 2);
+    }
+    cli::run_day_with_args(&Day3WithRules(rules), args.into_iter());
 }
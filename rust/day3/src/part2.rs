@@ -1,31 +1,24 @@
-use crate::part1::Grid;
+use crate::schematic::Schematic;
 
-const GEAR: char = '*';
-
-pub fn get_gear_ratios_and_delete(grid: &mut Grid) -> Vec<usize> {
-	let mut to_return = Vec::new();
-	for i in 0..(grid.grid.len()) {
-		if grid.grid[i] == GEAR {
-			let to_check = grid.get_surrounding_indicies_in_bounds(i);
-			let mut potential_parts: Vec<usize> = Vec::new();
-			for index in to_check {
-				if grid.grid[index].is_ascii_digit() {
-					potential_parts.push(grid.get_number_from_index_and_delete(index));
-				}
-			}
-			if potential_parts.len() == 2 {
-				to_return.push(potential_parts[0] * potential_parts[1]);
-			}
-		}
-	}
-	to_return
+// A gear touches exactly as many numbers as the rules say (two in the puzzle), and its ratio
+// combines them (their product in the puzzle).
+pub fn gear_ratios(schematic: &Schematic) -> Vec<usize> {
+	let rules = &schematic.rules;
+	schematic
+		.grid
+		.iter()
+		.filter(|(_, c)| rules.is_gear(**c))
+		.map(|(p, _)| schematic.numbers_next_to(p))
+		.filter(|numbers| numbers.len() == rules.gear_neighbours)
+		.map(|numbers| rules.combine.apply(numbers.iter().map(|n| n.value)))
+		.collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::part1::Grid;
+    use crate::{ rules::{ Combine, SchematicRules }, schematic::Schematic };
 
-    use super::get_gear_ratios_and_delete;
+    use super::gear_ratios;
 
     #[test]
     fn example() {
@@ -41,10 +34,28 @@ mod tests {
 ...$.*....
 .664.598.."
 		);
-		let mut grid = Grid::new(&input);
-		let gear_ratios = get_gear_ratios_and_delete(&mut grid);
-		let sum: usize = gear_ratios.iter().sum();
+		let lines: Vec<&str> = input.lines().collect();
+		let schematic = Schematic::from_lines(&lines[..]).unwrap();
+		let sum: usize = gear_ratios(&schematic).iter().sum();
 		let expected_sum: usize = 467835;
 		assert_eq!(expected_sum, sum);
 	}
+
+	#[test]
+	fn shared_number() {
+		// The 5 touches both gears, and counts for each.
+		let lines = ["2*5*3"];
+		let schematic = Schematic::from_lines(&lines).unwrap();
+		assert_eq!(vec![10, 15], gear_ratios(&schematic));
+		assert_eq!(vec![10, 15], gear_ratios(&schematic));
+	}
+
+	#[test]
+	fn other_gears() {
+		let lines = ["1.2", ".@.", "3.4"];
+		let rules = SchematicRules::new().gear('@').gear_neighbours(4).combine(Combine::Sum);
+		assert_eq!(vec![10], gear_ratios(&Schematic::with_rules(&lines, rules.clone()).unwrap()));
+		assert!(gear_ratios(&Schematic::with_rules(&lines, rules.gear_neighbours(2)).unwrap()).is_empty());
+		assert!(gear_ratios(&Schematic::from_lines(&lines).unwrap()).is_empty());
+	}
 }
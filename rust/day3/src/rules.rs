@@ -0,0 +1,286 @@
+use std::fmt::Display;
+
+use aoc_common::{ parse, ParseError };
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbols {
+    // Anything that isn't a digit or blank.
+    AnyOther,
+    // Only these. A schematic with anything else that isn't a digit, the blank or the gear is rejected.
+    Only(Vec<char>),
+}
+
+// How a gear's numbers make its ratio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Combine {
+    Product,
+    Sum,
+}
+impl Combine {
+    pub fn apply(&self, values: impl Iterator<Item = usize>) -> usize {
+        match self {
+            Combine::Product => values.product(),
+            Combine::Sum => values.sum(),
+        }
+    }
+}
+impl Display for Combine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combine::Product => write!(f, "product"),
+            Combine::Sum => write!(f, "sum"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchematicRules {
+    pub blank: char,
+    pub symbols: Symbols,
+    pub gear: char,
+    // A gear touches exactly this many numbers.
+    pub gear_neighbours: usize,
+    pub combine: Combine,
+    // Pad short rows with the blank instead of rejecting them.
+    pub pad_ragged_rows: bool,
+}
+// The puzzle's rules.
+impl Default for SchematicRules {
+    fn default() -> Self {
+        SchematicRules { blank: '.', symbols: Symbols::AnyOther, gear: '*', gear_neighbours: 2, combine: Combine::Product, pad_ragged_rows: false }
+    }
+}
+impl SchematicRules {
+    pub fn new() -> SchematicRules {
+        SchematicRules::default()
+    }
+    pub fn blank(mut self, blank: char) -> SchematicRules {
+        self.blank = blank;
+        self
+    }
+    pub fn symbols(mut self, symbols: &[char]) -> SchematicRules {
+        self.symbols = Symbols::Only(Vec::from(symbols));
+        self
+    }
+    pub fn gear(mut self, gear: char) -> SchematicRules {
+        self.gear = gear;
+        self
+    }
+    pub fn gear_neighbours(mut self, gear_neighbours: usize) -> SchematicRules {
+        self.gear_neighbours = gear_neighbours;
+        self
+    }
+    pub fn combine(mut self, combine: Combine) -> SchematicRules {
+        self.combine = combine;
+        self
+    }
+    pub fn pad_ragged_rows(mut self, pad_ragged_rows: bool) -> SchematicRules {
+        self.pad_ragged_rows = pad_ragged_rows;
+        self
+    }
+
+    pub fn is_symbol(&self, c: char) -> bool {
+        if c.is_ascii_digit() || c == self.blank {
+            return false;
+        }
+        match &self.symbols {
+            Symbols::AnyOther => true,
+            Symbols::Only(symbols) => symbols.contains(&c),
+        }
+    }
+    // A gear that's a digit or the blank would make numbers or empty space into gears, so it
+    // never matches anything. `from_lines` refuses one.
+    pub fn is_gear(&self, c: char) -> bool {
+        c == self.gear && !c.is_ascii_digit() && c != self.blank
+    }
+    // Whether `c` may appear in a schematic at all. Whitespace only may if it's the blank.
+    pub fn is_allowed(&self, c: char) -> bool {
+        if c.is_ascii_digit() || c == self.blank || c == self.gear {
+            return true;
+        }
+        match &self.symbols {
+            Symbols::AnyOther => !c.is_control() && !c.is_whitespace(),
+            Symbols::Only(symbols) => symbols.contains(&c),
+        }
+    }
+
+    // Reads a rules file like:
+    //
+    //     # Every option is optional, and these are the defaults.
+    //     blank = "."
+    //     symbols = any        # or the symbols themselves, like "*#+$"
+    //     gear = "*"
+    //     gear_neighbours = 2
+    //     combine = product    # or sum
+    //     pad_ragged_rows = false
+    pub fn from_lines(lines: &[&str]) -> Result<SchematicRules, ParseError> {
+        let mut rules = SchematicRules::default();
+        // Where the blank and symbols were set, to point at if they don't go together.
+        let mut blank_line: Option<usize> = None;
+        let mut symbols_line: Option<usize> = None;
+        let mut gear_line: Option<usize> = None;
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = strip_comment(line).trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let parse_line = |rules: &mut SchematicRules| -> Result<(), ParseError> {
+                let (key, value) = parse::split_once(line, trimmed, '=')?;
+                let (key, value) = (key.trim(), value.trim());
+                if value.is_empty() {
+                    return Err(parse::missing_after(line, trimmed, "a value"));
+                }
+                match key {
+                    "blank" => rules.blank = character(line, value)?,
+                    "gear" => rules.gear = character(line, value)?,
+                    "symbols" if value == "any" => rules.symbols = Symbols::AnyOther,
+                    "symbols" => rules.symbols = Symbols::Only(quoted(line, value)?.chars().collect()),
+                    "gear_neighbours" => rules.gear_neighbours = parse::parse_number(line, value)?,
+                    "combine" => rules.combine = match value {
+                        "product" => Combine::Product,
+                        "sum" => Combine::Sum,
+                        _ => return Err(parse::expected(line, value, "product or sum")),
+                    },
+                    "pad_ragged_rows" => rules.pad_ragged_rows = match value {
+                        "true" => true,
+                        "false" => false,
+                        _ => return Err(parse::expected(line, value, "true or false")),
+                    },
+                    _ => return Err(parse::expected(line, key, "blank, symbols, gear, gear_neighbours, combine or pad_ragged_rows")),
+                }
+                if key == "gear_neighbours" && rules.gear_neighbours == 0 {
+                    return Err(parse::invalid(line, value, String::from("a gear needs at least one number")));
+                }
+                Ok(())
+            };
+            parse_line(&mut rules).map_err(|e| e.on_line(i + 1))?;
+            match trimmed.split('=').next().map(|k| k.trim()) {
+                Some("blank") => blank_line = Some(i),
+                Some("symbols") => symbols_line = Some(i),
+                Some("gear") => gear_line = Some(i),
+                _ => (),
+            }
+        }
+        let whole_line = |i: usize, message: String| {
+            let line = strip_comment(lines[i]).trim();
+            parse::invalid(lines[i], line, message).on_line(i + 1)
+        };
+        if let (Some(i), true) = (blank_line, rules.blank.is_ascii_digit()) {
+            return Err(whole_line(i, String::from("the blank can't be a digit")));
+        }
+        if let (Some(i), Symbols::Only(symbols)) = (symbols_line, &rules.symbols) {
+            if let Some(c) = symbols.iter().find(|c| c.is_ascii_digit() || **c == rules.blank) {
+                return Err(whole_line(i, format!("'{}' can't be a symbol because it's a digit or the blank", c)));
+            }
+        }
+        // Only a changed blank can clash with the default gear.
+        if let (Some(i), true) = (gear_line.or(blank_line), rules.gear.is_ascii_digit() || rules.gear == rules.blank) {
+            return Err(whole_line(i, format!("'{}' can't be the gear because it's a digit or the blank", rules.gear)));
+        }
+        Ok(rules)
+    }
+}
+
+// Everything before a '#' that isn't in quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut in_quotes = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '#' if !in_quotes => return &line[..i],
+            _ => (),
+        }
+    }
+    line
+}
+
+fn quoted<'a>(line: &str, value: &'a str) -> Result<&'a str, ParseError> {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(|| parse::expected(line, value, "a quoted string"))
+}
+
+fn character(line: &str, value: &str) -> Result<char, ParseError> {
+    let text = quoted(line, value)?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(parse::expected(line, value, "a single character in quotes")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseErrorKind;
+
+    #[test]
+    fn symbols() {
+        let rules = SchematicRules::default();
+        assert!(rules.is_symbol('*'));
+        assert!(rules.is_symbol('x'));
+        assert!(!rules.is_symbol('.'));
+        assert!(!rules.is_symbol('7'));
+        let rules = SchematicRules::new().blank(' ').symbols(&['#', '@']);
+        assert!(rules.is_symbol('#'));
+        assert!(!rules.is_symbol('*'));
+        assert!(!rules.is_symbol(' '));
+        assert!(SchematicRules::default().is_gear('*'));
+        assert!(!SchematicRules::new().gear('5').is_gear('5'));
+        assert!(!SchematicRules::new().gear('.').is_gear('.'));
+    }
+
+    #[test]
+    fn allowed() {
+        let rules = SchematicRules::default();
+        assert!(rules.is_allowed('x'));
+        assert!(!rules.is_allowed(' '));
+        assert!(!rules.is_allowed('\t'));
+        let rules = SchematicRules::new().blank(' ').symbols(&['#']);
+        assert!(rules.is_allowed(' '));
+        assert!(rules.is_allowed('*'));
+        assert!(!rules.is_allowed('.'));
+    }
+
+    #[test]
+    fn reads_files() {
+        let lines = [
+            "# Spaces for blanks",
+            "blank = \" \"",
+            "symbols = \"#@*\"  # no others",
+            "gear = \"@\"",
+            "gear_neighbours = 3",
+            "combine = sum",
+            "pad_ragged_rows = true",
+        ];
+        let expected = SchematicRules::new().blank(' ').symbols(&['#', '@', '*']).gear('@').gear_neighbours(3).combine(Combine::Sum).pad_ragged_rows(true);
+        assert_eq!(Ok(expected), SchematicRules::from_lines(&lines));
+        assert_eq!(Ok(SchematicRules::default()), SchematicRules::from_lines(&["", "symbols = any"]));
+    }
+
+    #[test]
+    fn file_errors() {
+        let error = SchematicRules::from_lines(&["blank = \".\"", "gear = \"**\""]).unwrap_err();
+        assert_eq!((2, 8), (error.line, error.column));
+        let error = SchematicRules::from_lines(&["blank = \".\"", "gear = \"5\""]).unwrap_err();
+        assert_eq!((2, 1, 10), (error.line, error.column, error.len));
+        let error = SchematicRules::from_lines(&["gear = \".\"", "combine = sum"]).unwrap_err();
+        assert_eq!(1, error.line);
+        let error = SchematicRules::from_lines(&["blank = \"*\""]).unwrap_err();
+        assert_eq!(ParseErrorKind::Invalid(String::from("'*' can't be the gear because it's a digit or the blank")), error.kind);
+        let error = SchematicRules::from_lines(&["combine = max"]).unwrap_err();
+        assert_eq!(11, error.column);
+        let error = SchematicRules::from_lines(&["gears = \"*\""]).unwrap_err();
+        assert_eq!((1, 5), (error.column, error.len));
+        assert!(SchematicRules::from_lines(&["gear_neighbours = 0"]).is_err());
+        assert!(SchematicRules::from_lines(&["blank = \"0\""]).is_err());
+        let rules = SchematicRules::from_lines(&["symbols = \"*.\"", "blank = \"~\""]).unwrap();
+        assert!(rules.is_symbol('.'));
+        let error = SchematicRules::from_lines(&["symbols = \"*.\""]).unwrap_err();
+        assert_eq!((1, 1, 14), (error.line, error.column, error.len));
+        assert!(SchematicRules::from_lines(&["blank"]).is_err());
+        let error = SchematicRules::from_lines(&["pad_ragged_rows = yes"]).unwrap_err();
+        assert_eq!((19, 3), (error.column, error.len));
+    }
+}
@@ -0,0 +1,238 @@
+use aoc_common::{ parse, Grid, ParseError, ParseErrorKind, Point };
+
+use crate::rules::SchematicRules;
+
+// How many offending rows or characters an error lists before giving up.
+const LISTED: usize = 5;
+
+// Every row must be as wide as the first, unless the rules pad short rows out to the widest, and every
+// character must be allowed by the rules. Errors point at the first problem and list the rest.
+pub fn grid_from_lines(lines: &[&str], rules: &SchematicRules) -> Result<Grid<char>, ParseError> {
+    let widths: Vec<usize> = lines.iter().map(|l| l.chars().count()).collect();
+    let width = match rules.pad_ragged_rows {
+        true => widths.iter().copied().max().unwrap_or(0),
+        false => widths.first().copied().unwrap_or(0),
+    };
+    if width == 0 {
+        return Err(ParseError::new(ParseErrorKind::Missing { expected: String::from("a schematic") }, 1, 1));
+    }
+    if !rules.pad_ragged_rows {
+        let ragged: Vec<usize> = (0..lines.len()).filter(|i| widths[*i] != width).collect();
+        if let Some(&first) = ragged.first() {
+            let rows = ragged.iter().map(|i| format!("row {} is {}", i + 1, widths[*i])).collect();
+            let message = format!("every row should be {} characters wide like row 1, but {}", width, listed(rows));
+            return Err(parse::invalid(lines[first], lines[first], message).on_line(first + 1));
+        }
+    }
+
+    let unexpected: Vec<(usize, usize, char)> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| line.chars().enumerate().map(move |(column, c)| (row, column, c)))
+        .filter(|(_, _, c)| !rules.is_allowed(*c))
+        .collect();
+    if let Some(&(row, column, _)) = unexpected.first() {
+        let found = unexpected.iter().map(|(row, column, c)| format!("{:?} at row {} column {}", c, row + 1, column + 1)).collect();
+        let message = format!("unexpected characters: {}", listed(found));
+        return Err(ParseError::new(ParseErrorKind::Invalid(message), column + 1, 1).on_line(row + 1));
+    }
+
+    let cells = lines
+        .iter()
+        .zip(widths)
+        .flat_map(|(line, w)| line.chars().chain(std::iter::repeat_n(rules.blank, width - w)))
+        .collect();
+    Ok(Grid::from_vec(width, cells).expect("every row has been checked or padded to the same width"))
+}
+
+// "a", "a and b", "a, b and c", or "a, b, c, d, e and 3 more".
+fn listed(mut items: Vec<String>) -> String {
+    if items.len() > LISTED {
+        let more = items.len() - LISTED;
+        items.truncate(LISTED);
+        items.push(format!("{} more", more));
+    }
+    match items.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+// A number written left to right on one row, from column `start` up to but not including `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub value: usize,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+impl NumberSpan {
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let row = self.row;
+        (self.start..self.end).map(move |x| Point::new(x, row))
+    }
+}
+
+// The grid, read once into the numbers and symbols on it. Nothing here changes the grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<NumberSpan>,
+    pub symbols: Vec<Point>,
+    pub rules: SchematicRules,
+    // Which of `numbers` covers each point, if any.
+    number_at: Grid<Option<usize>>,
+}
+impl Schematic {
+    pub fn from_lines(lines: &[&str]) -> Result<Schematic, ParseError> {
+        Schematic::with_rules(lines, SchematicRules::default())
+    }
+    pub fn with_rules(lines: &[&str], rules: SchematicRules) -> Result<Schematic, ParseError> {
+        let grid = grid_from_lines(lines, &rules)?;
+        let mut numbers: Vec<NumberSpan> = Vec::new();
+        let mut number_at: Grid<Option<usize>> = Grid::filled(grid.width(), grid.height(), None);
+        for (row, line) in lines.iter().enumerate() {
+            // Byte and column of the first digit of the number being read.
+            let mut number_start: Option<(usize, usize)> = None;
+            let ends = line.char_indices().map(Some).chain([None]);
+            for (column, c) in ends.enumerate() {
+                match (c, number_start) {
+                    (Some((byte, c)), None) if c.is_ascii_digit() => number_start = Some((byte, column)),
+                    (Some((_, c)), Some(_)) if c.is_ascii_digit() => (),
+                    (c, Some((start_byte, start))) => {
+                        let end_byte = c.map_or(line.len(), |(byte, _)| byte);
+                        let value = parse::parse_number(line, &line[start_byte..end_byte]).map_err(|e| e.on_line(row + 1))?;
+                        let span = NumberSpan { value, row, start, end: column };
+                        for point in span.points() {
+                            number_at[point] = Some(numbers.len());
+                        }
+                        numbers.push(span);
+                        number_start = None;
+                    },
+                    (_, None) => (),
+                }
+            }
+        }
+        let symbols = grid.iter().filter(|(_, c)| rules.is_symbol(**c)).map(|(p, _)| p).collect();
+        Ok(Schematic { grid, numbers, symbols, rules, number_at })
+    }
+
+    pub fn number_at(&self, point: Point) -> Option<&NumberSpan> {
+        self.number_at.get(point).copied().flatten().map(|i| &self.numbers[i])
+    }
+    // Each number touching `point`, including diagonally, once.
+    pub fn numbers_next_to(&self, point: Point) -> Vec<&NumberSpan> {
+        let mut to_return: Vec<&NumberSpan> = Vec::new();
+        for span in self.grid.neighbours8(point).filter_map(|p| self.number_at(p)) {
+            if !to_return.contains(&span) {
+                to_return.push(span);
+            }
+        }
+        to_return
+    }
+    pub fn symbols_next_to(&self, span: &NumberSpan) -> Vec<Point> {
+        let from = Point::new(span.start.saturating_sub(1), span.row.saturating_sub(1));
+        let to = Point::new(span.end, span.row + 1);
+        self.grid.region(from, to).filter(|(_, c)| self.rules.is_symbol(**c)).map(|(p, _)| p).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 10] = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+        ".....+.58.",
+        "..592.....",
+        "......755.",
+        "...$.*....",
+        ".664.598..",
+    ];
+
+    #[test]
+    fn tokenises() {
+        let schematic = Schematic::from_lines(&EXAMPLE).unwrap();
+        assert_eq!(10, schematic.numbers.len());
+        assert_eq!(NumberSpan { value: 467, row: 0, start: 0, end: 3 }, schematic.numbers[0]);
+        assert_eq!(NumberSpan { value: 114, row: 0, start: 5, end: 8 }, schematic.numbers[1]);
+        assert_eq!(NumberSpan { value: 598, row: 9, start: 5, end: 8 }, schematic.numbers[9]);
+        assert_eq!(6, schematic.symbols.len());
+        assert_eq!(Some(467), schematic.number_at(Point::new(2, 0)).map(|n| n.value));
+        assert_eq!(None, schematic.number_at(Point::new(3, 0)));
+
+        let numbers = Schematic::from_lines(&["12.3", "..45"]).unwrap().numbers;
+        assert_eq!(vec![(12, 0, 0, 2), (3, 0, 3, 4), (45, 1, 2, 4)], numbers.iter().map(|n| (n.value, n.row, n.start, n.end)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn adjacency() {
+        let schematic = Schematic::from_lines(&EXAMPLE).unwrap();
+        let values: Vec<usize> = schematic.numbers_next_to(Point::new(3, 1)).iter().map(|n| n.value).collect();
+        assert_eq!(vec![35, 467], values);
+        assert_eq!(vec![Point::new(3, 1)], schematic.symbols_next_to(&schematic.numbers[0]));
+        assert!(schematic.symbols_next_to(&schematic.numbers[1]).is_empty());
+        // Asking twice gives the same answer.
+        assert_eq!(schematic.numbers_next_to(Point::new(3, 1)), schematic.numbers_next_to(Point::new(3, 1)));
+    }
+
+    #[test]
+    fn other_rules() {
+        let rules = SchematicRules::new().blank(' ').symbols(&['#']);
+        let schematic = Schematic::with_rules(&["12 #", "3*4 "], rules.clone()).unwrap();
+        assert_eq!(vec![Point::new(3, 0)], schematic.symbols);
+        assert!(schematic.symbols_next_to(&schematic.numbers[1]).is_empty());
+        // '.' isn't the blank any more, or a symbol.
+        let error = Schematic::with_rules(&["12 #", "3.4 "], rules).unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = Schematic::from_lines(&["12..", "..*", "3...", "4.....", ".."]).unwrap_err();
+        assert_eq!((2, 1, 3), (error.line, error.column, error.len));
+        assert_eq!(
+            ParseErrorKind::Invalid(String::from("every row should be 4 characters wide like row 1, but row 2 is 3, row 4 is 6 and row 5 is 2")),
+            error.kind
+        );
+
+        let rules = SchematicRules::new().pad_ragged_rows(true);
+        let schematic = Schematic::with_rules(&["12", "..*", "3"], rules).unwrap();
+        assert_eq!((3, 3), (schematic.grid.width(), schematic.grid.height()));
+        assert_eq!(Some(&'.'), schematic.grid.get(Point::new(2, 0)));
+        assert_eq!(vec![12, 3], schematic.numbers.iter().map(|n| n.value).collect::<Vec<_>>());
+        assert_eq!(vec![Point::new(2, 1)], schematic.symbols_next_to(&schematic.numbers[0]));
+    }
+
+    #[test]
+    fn unexpected_characters() {
+        let error = Schematic::from_lines(&["1.\t.", "....", " ..\u{7}"]).unwrap_err();
+        assert_eq!((1, 3), (error.line, error.column));
+        assert_eq!(
+            ParseErrorKind::Invalid(String::from("unexpected characters: '\\t' at row 1 column 3, ' ' at row 3 column 1 and '\\u{7}' at row 3 column 4")),
+            error.kind
+        );
+        let many = ["      "];
+        let error = Schematic::from_lines(&many).unwrap_err();
+        assert!(error.kind.to_string().ends_with("' ' at row 1 column 5 and 1 more"));
+    }
+
+    #[test]
+    fn empty() {
+        let error = Schematic::from_lines(&[]).unwrap_err();
+        assert_eq!(ParseErrorKind::Missing { expected: String::from("a schematic") }, error.kind);
+        assert!(Schematic::from_lines(&["", ""]).is_err());
+        assert!(Schematic::with_rules(&[""], SchematicRules::new().pad_ragged_rows(true)).is_err());
+    }
+
+    #[test]
+    fn huge_number() {
+        let error = Schematic::from_lines(&["...............................", ".123456789012345678901234567890"]).unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+}
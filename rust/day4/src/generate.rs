@@ -0,0 +1,74 @@
+use aoc_common::Rng;
+
+const WINNERS: usize = 5;
+const GUESSES: usize = 8;
+// Cards won in part 2 can grow exponentially, so wins are held back once the total gets this big.
+const MAX_TOTAL_CARDS: usize = 1_000_000_000;
+
+fn numbers_string(numbers: &[usize]) -> String {
+    numbers.iter().map(|n| format!("{:>2}", n)).collect::<Vec<String>>().join(" ")
+}
+
+// A card whose guesses match exactly `matches` of its winning numbers.
+pub fn card_line(rng: &mut Rng, id: usize, matches: usize) -> String {
+    let numbers = rng.distinct(1..100, WINNERS + GUESSES - matches);
+    let winners = &numbers[..WINNERS];
+    let mut guesses: Vec<usize> = winners[..matches].to_vec();
+    guesses.extend(&numbers[WINNERS..]);
+    rng.shuffle(&mut guesses[..]);
+    format!("Card {:>3}: {} | {}", id, numbers_string(winners), numbers_string(&guesses[..]))
+}
+
+// No card wins copies of cards past the end of the table, which part 2 doesn't allow.
+pub fn generate(rng: &mut Rng, cards: usize) -> Vec<String> {
+    let cards = cards.max(1);
+    let mut copies: Vec<usize> = vec![1; cards];
+    let mut total = cards;
+    let mut to_return: Vec<String> = Vec::new();
+    for i in 0..cards {
+        let most = WINNERS.min(cards - i - 1);
+        let mut matches = if rng.chance(0.5) { 0 } else { rng.range(0..(most + 1)) };
+        if total + copies[i] * matches > MAX_TOTAL_CARDS {
+            matches = 0;
+        }
+        for j in (i + 1)..(i + 1 + matches) {
+            copies[j] += copies[i];
+        }
+        total += copies[i] * matches;
+        to_return.push(card_line(rng, i + 1, matches));
+    }
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let cards = 1 + seed as usize * 4;
+            let lines = generate(&mut Rng::new(seed), cards);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            solve_part1(&lines_str[..]).unwrap();
+            let total = solve_part2(&lines_str[..]).unwrap();
+            assert!((cards..=MAX_TOTAL_CARDS).contains(&total));
+        }
+    }
+
+    #[test]
+    fn matches_are_exact() {
+        let mut rng = Rng::new(3);
+        for matches in 0..=WINNERS {
+            let line = card_line(&mut rng, 1, matches);
+            let expected = if matches == 0 { 0 } else { 1 << (matches - 1) };
+            assert_eq!(expected, solve_part1(&[&line]).unwrap());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
@@ -0,0 +1,38 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    Ok(part1::cards_from_lines(input)?
+        .iter()
+        .map(|card| card.get_score())
+        .sum())
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    let mut cards = part2::cards_from_lines(input)?;
+    part2::update_cards_after_winnings(&mut cards[..]);
+    Ok(part2::get_total_cards(&cards[..]))
+}
+
+pub struct Day4;
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        part1::cards_from_lines(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
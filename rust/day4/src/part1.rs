@@ -1,19 +1,22 @@
 use std::str::FromStr;
 
-#[derive(Debug)]
-pub struct InvalidFormat;
+use aoc_common::{ parse, ParseError };
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Card {
     id: usize,
-    winners: Vec<usize>,
-    guesses: Vec<usize>,
+    pub(crate) winners: Vec<usize>,
+    pub(crate) guesses: Vec<usize>,
 }
 impl Card {
+    pub fn id(&self) -> usize {
+        self.id
+    }
     fn get_winning_numbers(&self) -> Vec<usize> {
         self.guesses
             .iter()
             .filter(|n| self.winners.contains(n))
-            .map(|n| *n)
+            .copied()
             .collect()
     }
     pub fn get_score(&self) -> usize {
@@ -26,51 +29,24 @@ impl Card {
     }
 }
 impl FromStr for Card {
-    type Err = InvalidFormat;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut colon_split = s.split(':');
-        let card_part = match colon_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let other_part = match colon_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let id: usize = (&card_part[5..(card_part.len())]).trim().parse().unwrap();
-        let mut pipe_split = other_part.split('|');
-        let winning_numbers_str = match pipe_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let guessed_numbers_str = match pipe_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let winning_numbers: Vec<usize> = winning_numbers_str
-            .split(' ')
-            .filter(|s| s != &"")
-            .map(|s| s.parse().unwrap())
-            .collect();
-        let guessed_numbers: Vec<usize> = guessed_numbers_str
-            .split(' ')
-            .filter(|s| s != &"")
-            .map(|s| s.parse().unwrap())
-            .collect();
+        let (card_part, other_part) = parse::split_once(s, s, ':')?;
+        let id: usize = parse::parse_number(s, parse::expect_prefix(s, card_part, "Card")?.trim())?;
+        let (winning_numbers_str, guessed_numbers_str) = parse::split_once(s, other_part, '|')?;
+        let winning_numbers: Vec<usize> = parse::numbers(s, winning_numbers_str)?;
+        let guessed_numbers: Vec<usize> = parse::numbers(s, guessed_numbers_str)?;
 
         Ok(Card {
             id,
             winners: winning_numbers,
             guesses: guessed_numbers,
         })
-
     }
 }
 
-pub fn cards_from_lines(slice: &[&str]) -> Vec<Card> {
-    slice.iter()
-        .map(|s| Card::from_str(s).unwrap())
-        .collect()
+pub fn cards_from_lines(slice: &[&str]) -> Result<Vec<Card>, ParseError> {
+    parse::parse_lines(slice, Card::from_str)
 }
 
 #[cfg(test)]
@@ -88,7 +64,7 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         );
         let lines: Vec<&str> = input.lines().collect();
-        let cards: Vec<Card> = cards_from_lines(&lines[..]);
+        let cards: Vec<Card> = cards_from_lines(&lines[..]).unwrap();
         let scores: Vec<usize> = cards.iter().map(|card| card.get_score()).collect();
         let expected_scores: Vec<usize> = vec![8, 2, 2, 1, 0, 0];
         assert_eq!(expected_scores, scores);
@@ -98,4 +74,19 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         let expected_sum: usize = 13;
         assert_eq!(expected_sum, sum);
     }
-}
\ No newline at end of file
+    #[test]
+    fn parse_errors() {
+        let lines = ["Card 1: 41 48 | 83 86", "Card 2: 13 32 16 61 30"];
+        let error = cards_from_lines(&lines[..]).unwrap_err();
+        assert_eq!((2, 23), (error.line, error.column));
+
+        let error = Card::from_str("Card x: 41 | 83").unwrap_err();
+        assert_eq!((6, 1), (error.column, error.len));
+
+        let error = Card::from_str("Crad 1: 41 | 83").unwrap_err();
+        assert_eq!((1, 4), (error.column, error.len));
+
+        let error = Card::from_str("Card 1: 41 | 8e").unwrap_err();
+        assert_eq!(14, error.column);
+    }
+}
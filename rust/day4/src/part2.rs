@@ -1,14 +1,16 @@
 use std::str::FromStr;
 
+use aoc_common::{ parse, ParseError };
+
+use crate::part1;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct WinResult {
     pub card_id: usize,
     pub copies_to_add: usize,
 }
 
-#[derive(Debug)]
-pub struct InvalidFormat;
-
+#[derive(Debug, Clone, PartialEq)]
 pub struct Card {
     id: usize,
     winners: Vec<usize>,
@@ -16,11 +18,17 @@ pub struct Card {
     copies: usize,
 }
 impl Card {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+    pub fn copies(&self) -> usize {
+        self.copies
+    }
     fn get_winning_numbers(&self) -> Vec<usize> {
         self.guesses
             .iter()
             .filter(|n| self.winners.contains(n))
-            .map(|n| *n)
+            .copied()
             .collect()
     }
     pub fn get_copies_of_cards(&self) -> Vec<WinResult> {
@@ -32,45 +40,15 @@ impl Card {
     }
 }
 impl FromStr for Card {
-    type Err = InvalidFormat;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut colon_split = s.split(':');
-        let card_part = match colon_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let other_part = match colon_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let id: usize = (&card_part[5..(card_part.len())]).trim().parse().unwrap();
-        let mut pipe_split = other_part.split('|');
-        let winning_numbers_str = match pipe_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let guessed_numbers_str = match pipe_split.next() {
-            Some(str) => str,
-            None => return Err(InvalidFormat),
-        };
-        let winning_numbers: Vec<usize> = winning_numbers_str
-            .split(' ')
-            .filter(|s| s != &"")
-            .map(|s| s.parse().unwrap())
-            .collect();
-        let guessed_numbers: Vec<usize> = guessed_numbers_str
-            .split(' ')
-            .filter(|s| s != &"")
-            .map(|s| s.parse().unwrap())
-            .collect();
-
+        let card = part1::Card::from_str(s)?;
         Ok(Card {
-            id,
+            id: card.id(),
             copies: 1,
-            winners: winning_numbers,
-            guesses: guessed_numbers,
+            winners: card.winners,
+            guesses: card.guesses,
         })
-
     }
 }
 
@@ -94,10 +72,8 @@ pub fn get_total_cards(cards: &[Card]) -> usize {
         .sum()
 }
 
-pub fn cards_from_lines(slice: &[&str]) -> Vec<Card> {
-    slice.iter()
-        .map(|s| Card::from_str(s).unwrap())
-        .collect()
+pub fn cards_from_lines(slice: &[&str]) -> Result<Vec<Card>, ParseError> {
+    parse::parse_lines(slice, Card::from_str)
 }
 
 #[cfg(test)]
@@ -115,7 +91,7 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         );
         let lines: Vec<&str> = input.lines().collect();
-        let mut cards: Vec<Card> = cards_from_lines(&lines[..]);
+        let mut cards: Vec<Card> = cards_from_lines(&lines[..]).unwrap();
         update_cards_after_winnings(&mut cards[..]);
 
         let copies: Vec<usize> = cards.iter().map(|card| card.copies).collect();
@@ -0,0 +1,69 @@
+use aoc_common::Rng;
+
+use crate::part1::MAP_COUNT;
+
+const CATEGORIES: [&str; MAP_COUNT + 1] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+// Part 2 tries every seed in every range, so the ranges stay short.
+const MAX_SEED_RANGE: usize = 50;
+
+// Ranges in a map don't overlap, so it doesn't matter which one is tried first.
+fn map_lines(rng: &mut Rng, ranges: usize, limit: usize) -> Vec<String> {
+    let step = limit / ranges;
+    (0..ranges)
+        .map(|i| {
+            let length = rng.range(1..(step + 1));
+            let source = i * step + rng.range(0..(step - length + 1));
+            let destination = rng.range(0..limit);
+            format!("{} {} {}", destination, source, length)
+        })
+        .collect()
+}
+
+// An almanac with `size` seed ranges and `size` ranges in each map.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let limit = 100 * size;
+    let seeds: Vec<String> = (0..size)
+        .flat_map(|_| [rng.range(0..limit), rng.range(1..(MAX_SEED_RANGE + 1))])
+        .map(|n| n.to_string())
+        .collect();
+    let mut to_return: Vec<String> = vec![format!("seeds: {}", seeds.join(" "))];
+    for pair in CATEGORIES.windows(2) {
+        to_return.push(String::new());
+        to_return.push(format!("{}-to-{} map:", pair[0], pair[1]));
+        to_return.extend(map_lines(rng, size, limit));
+    }
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ parse_input_lines, solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..30 {
+            let size = 1 + seed as usize;
+            let lines = generate(&mut Rng::new(seed), size);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert_eq!(2 * size, parse_input_lines(&lines_str[..]).unwrap().0.len());
+            solve_part1(&lines_str[..]).unwrap();
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::{ parse, ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::{ parse_input_lines, Mapper, Seed };
+pub use part2::parse_seeds_line;
+
+pub fn lowest_location(seeds: &[Seed], mapper: &Mapper) -> Option<usize> {
+    seeds.iter().map(|seed| mapper.map_all(*seed)).min()
+}
+
+fn no_seeds(seeds_line: &str) -> ParseError {
+    parse::missing_after(seeds_line, seeds_line, "at least one seed")
+}
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    let (seeds, mapper) = parse_input_lines(input)?;
+    lowest_location(&seeds[..], &mapper).ok_or_else(|| no_seeds(input[0]))
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    let (_, mapper) = parse_input_lines(input)?;
+    let seeds = parse_seeds_line(input[0])?;
+    lowest_location(&seeds[..], &mapper).ok_or_else(|| no_seeds(input[0]))
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        parse_input_lines(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
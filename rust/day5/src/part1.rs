@@ -1,3 +1,5 @@
+use aoc_common::{ parse, ParseError };
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Range {
     from: usize,
@@ -15,6 +17,7 @@ impl Range {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 struct Map {
     ranges: Vec<Range>,
 }
@@ -23,9 +26,8 @@ impl Map {
         // This will loop through the ranges, try each one.
         // If it successfully mapped, then return.
         for r in &self.ranges {
-            match r.convert(to_map) {
-                Some(n) => return n,
-                None => (),
+            if let Some(n) = r.convert(to_map) {
+                return n
             }
         }
         // If the map never worked, then simply use the same number.
@@ -33,6 +35,7 @@ impl Map {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Mapper {
     maps: Vec<Map>
 }
@@ -44,59 +47,55 @@ impl Mapper {
 
 pub type Seed = usize;
 
-#[derive(Debug)]
-pub struct InvalidInput;
+pub const MAP_COUNT: usize = 7;
 
-pub fn parse_input_lines(s: &[&str]) -> Result<(Vec<Seed>, Mapper), InvalidInput> {
-    if s.is_empty() {
-        return Err(InvalidInput)
-    }
-    if !s[0].starts_with("seeds: ") {
-        return Err(InvalidInput)
-    }
-    let mut seeds: Vec<Seed> = Vec::new();
-    let seeds_str_iter = s[0][7..(s[0].len())].split(' ');
-    for seed in seeds_str_iter {
-        match seed.parse::<usize>() {
-            Ok(n) => seeds.push(n),
-            Err(_) => return Err(InvalidInput),
-        }
-    }
+pub fn parse_seeds(line: &str) -> Result<Vec<Seed>, ParseError> {
+    parse::numbers(line, parse::expect_prefix(line, line, "seeds: ")?)
+}
 
-    let mut maps: Vec<Map> = Vec::new();
+fn parse_range(line: &str) -> Result<Range, ParseError> {
+    let numbers: Vec<usize> = parse::numbers(line, line)?;
+    match numbers[..] {
+        // Converting adds the length to both ends, so neither can run past the biggest number.
+        [to, from, range] if to.checked_add(range).is_none() || from.checked_add(range).is_none() => {
+            let length = line.split_whitespace().nth(2).unwrap_or(line);
+            Err(parse::invalid(line, length, format!("a range of {} from {} to {} goes past {}", range, from, to, usize::MAX)))
+        },
+        [to, from, range] => Ok(Range { to, from, range }),
+        _ => Err(parse::invalid(line, line, format!(
+            "a map line should have 3 numbers (destination, source, length), found {}",
+            numbers.len(),
+        ))),
+    }
+}
 
-    let mut input_iter = s[2..(s.len())].iter();
-    let mut next_line = input_iter.next();
-    let mut next_map = Map {
-        ranges: Vec::new(),
+pub fn parse_input_lines(s: &[&str]) -> Result<(Vec<Seed>, Mapper), ParseError> {
+    let Some(first_line) = s.first() else {
+        return Err(parse::missing_after("", "", "a line of seeds"))
     };
-    while next_line.is_some() {
-        let line = next_line.unwrap();
-        if line == &"" {
-            maps.push(next_map);
-            next_map = Map { ranges: Vec::new() }
+    let seeds = parse_seeds(first_line)?;
+
+    // Each map starts with its "x-to-y map:" header and goes on until the next one.
+    let mut maps: Vec<Map> = Vec::new();
+    for (i, line) in s.iter().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue
         }
-        else if !line.ends_with(":") {
-            let mut ranges_iter = line.split(' ');
-            let to = match ranges_iter.next().unwrap().parse::<usize>() {
-                Ok(n) => n,
-                Err(_) => return Err(InvalidInput),
-            };
-            let from = match ranges_iter.next().unwrap().parse::<usize>() {
-                Ok(n) => n,
-                Err(_) => return Err(InvalidInput),
-            };
-            let range = match ranges_iter.next().unwrap().parse::<usize>() {
-                Ok(n) => n,
-                Err(_) => return Err(InvalidInput),
-            };
-            next_map.ranges.push(Range { to, from, range });
+        else if line.ends_with(':') {
+            maps.push(Map { ranges: Vec::new() });
+        }
+        else {
+            let range = parse_range(line).map_err(|e| e.on_line(i + 1))?;
+            match maps.last_mut() {
+                Some(map) => map.ranges.push(range),
+                None => return Err(parse::expected(line, line, "a map header such as \"seed-to-soil map:\"").on_line(i + 1)),
+            }
         }
-        next_line = input_iter.next();
     }
-    maps.push(next_map);
-    if maps.len() != 7 {
-        return Err(InvalidInput)
+    if maps.len() != MAP_COUNT {
+        let last_line = s.last().unwrap();
+        return Err(parse::invalid(last_line, last_line, format!("expected {} maps, found {}", MAP_COUNT, maps.len()))
+            .on_line(s.len()))
     }
 
     let mapper = Mapper { maps };
@@ -154,4 +153,31 @@ humidity-to-location map:
         let actual_lowest: usize = 35;
         assert_eq!(actual_lowest, lowest);
     }
+    #[test]
+    fn parse_errors() {
+        let error = parse_input_lines(&["seeds: 79 1x"]).unwrap_err();
+        assert_eq!((1, 11, 2), (error.line, error.column, error.len));
+
+        let error = parse_input_lines(&["seed: 79"]).unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
+
+        let lines = ["seeds: 79", "", "seed-to-soil map:", "50 98 2", "18446744073709551615 0 2"];
+        let error = parse_input_lines(&lines).unwrap_err();
+        assert_eq!((5, 24, 1), (error.line, error.column, error.len));
+
+        let lines = ["seeds: 79", "", "seed-to-soil map:", "50 98 2", "52 50"];
+        let error = parse_input_lines(&lines).unwrap_err();
+        assert_eq!((5, 1, 5), (error.line, error.column, error.len));
+
+        let lines = ["seeds: 79", "", "50 98 2"];
+        let error = parse_input_lines(&lines).unwrap_err();
+        assert_eq!(3, error.line);
+
+        let lines = ["seeds: 79", "", "seed-to-soil map:", "50 98 2"];
+        let error = parse_input_lines(&lines).unwrap_err();
+        assert_eq!(4, error.line);
+        assert_eq!("expected 7 maps, found 1", error.kind.to_string());
+
+        assert!(parse_input_lines(&[]).is_err());
+    }
 }
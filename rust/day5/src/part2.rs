@@ -1,25 +1,24 @@
+use aoc_common::{ parse, ParseError };
+
 use crate::part1::*;
 
-pub fn parse_seeds_line(s: &str) -> Result<Vec<Seed>, InvalidInput> {
-	let mut to_return: Vec<Seed> = Vec::new();
-	let mut seeds_iter = s[7..(s.len())].split(' ');
-	let mut next_seed_pair = (seeds_iter.next(), seeds_iter.next());
-	while next_seed_pair.0.is_some() && next_seed_pair.1.is_some() {
-		let start: usize = match next_seed_pair.0.unwrap().parse() {
-			Ok(n) => n,
-			Err(_) => return Err(InvalidInput),
-		};
-		let range: usize = match next_seed_pair.1.unwrap().parse() {
-			Ok(n) => n,
-			Err(_) => return Err(InvalidInput),
-		};
-		for i in start..(start + range) {
-			to_return.push(i);
-		}
-		next_seed_pair = (seeds_iter.next(), seeds_iter.next());
-		eprintln!("{:?}", next_seed_pair);
-	}
-	Ok(to_return)
+// The seeds line of part 2 is made of pairs of a starting seed and how many follow it.
+pub fn parse_seeds_line(s: &str) -> Result<Vec<Seed>, ParseError> {
+    let numbers = parse_seeds(s)?;
+    if numbers.len() % 2 == 1 {
+        return Err(parse::missing_after(s, s, "the length of the last range of seeds"))
+    }
+    // The tokens are the "seeds:" label and then the numbers.
+    let lengths = s.split_whitespace().skip(2).step_by(2);
+    for (pair, length) in numbers.chunks(2).zip(lengths) {
+        if pair[0].checked_add(pair[1]).is_none() {
+            return Err(parse::invalid(s, length, format!("{} seeds from {} goes past {}", pair[1], pair[0], usize::MAX)))
+        }
+    }
+    Ok(numbers
+        .chunks(2)
+        .flat_map(|pair| pair[0]..(pair[0] + pair[1]))
+        .collect())
 }
 
 #[cfg(test)]
@@ -78,4 +77,14 @@ humidity-to-location map:
         assert_eq!(actual_lowest, lowest);
 
 	}
-}
\ No newline at end of file
+    #[test]
+    fn odd_seeds_line() {
+        let error = parse_seeds_line("seeds: 79 14 55").unwrap_err();
+        assert_eq!(16, error.column);
+    }
+    #[test]
+    fn seeds_past_the_end() {
+        let error = parse_seeds_line("seeds: 79 14 18446744073709551615 2").unwrap_err();
+        assert_eq!((35, 1), (error.column, error.len));
+    }
+}
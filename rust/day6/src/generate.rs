@@ -0,0 +1,57 @@
+use aoc_common::Rng;
+
+// Part 2 tries every way of holding the button in one big race, made by gluing the
+// numbers together, so there are never more than three two digit times. `aoc generate`'s
+// help says so, since --size stops making a difference past this.
+const MAX_RACES: usize = 3;
+
+fn best_distance(time: usize) -> usize {
+    (time / 2) * (time - time / 2)
+}
+
+pub fn generate(rng: &mut Rng, races: usize) -> Vec<String> {
+    let races = races.clamp(1, MAX_RACES);
+    let times: Vec<usize> = (0..races).map(|_| rng.range(10..100)).collect();
+    // Every record can be beaten. Keeping the first one to a single digit also keeps
+    // part 2's glued together record below its best distance, so that can be beaten too.
+    let distances: Vec<usize> = times
+        .iter()
+        .enumerate()
+        .map(|(i, time)| rng.range(0..if i == 0 { 10 } else { best_distance(*time) }))
+        .collect();
+    let widths: Vec<usize> = distances.iter().map(|d| d.to_string().len().max(2)).collect();
+    let column = |numbers: &[usize]| -> String {
+        numbers.iter().zip(&widths).map(|(n, w)| format!("{:>w$}", n, w = w + 2)).collect()
+    };
+    vec![format!("Time:    {}", column(&times[..])), format!("Distance:{}", column(&distances[..]))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..20 {
+            let lines = generate(&mut Rng::new(seed), 1 + seed as usize % MAX_RACES);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert!(solve_part1(&lines_str[..]).unwrap() > 0);
+            assert!(solve_part2(&lines_str[..]).unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn size_is_the_number_of_races() {
+        for (size, races) in [(0, 1), (1, 1), (2, 2), (3, 3), (4, 3), (100, 3)] {
+            let lines = generate(&mut Rng::new(1), size);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert_eq!(races, crate::part1::parse_lines(&lines_str[..]).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 3), generate(&mut Rng::new(7), 3));
+    }
+}
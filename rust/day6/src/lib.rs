@@ -0,0 +1,38 @@
+use aoc_common::{ parse, ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::Race;
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    part1::parse_lines(input)?
+        .iter()
+        .try_fold(1_usize, |product, race| product.checked_mul(race.number_of_ways_to_win()))
+        .ok_or_else(|| parse::invalid(input[0], input[0], String::from("the product of the ways to win is too big")))
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    Ok(part2::parse_lines(input)?.number_of_ways_to_win())
+}
+
+pub struct Day6;
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        part1::parse_lines(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
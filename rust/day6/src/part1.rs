@@ -1,4 +1,4 @@
-
+use aoc_common::{ parse, ParseError };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Race {
@@ -18,23 +18,52 @@ impl Race {
     }
 }
 
-pub fn parse_numbers_string(s: &str) -> impl Iterator<Item = usize> + '_ {
-    s
-        .trim()
-        .split(' ')
-        .filter(|s| s != &"")
-        .map(|s| s.parse::<usize>().unwrap())
+pub fn parse_numbers_string(line: &str, s: &str) -> Result<Vec<usize>, ParseError> {
+    parse::numbers(line, s)
+}
+
+// The furthest a race of `time` can go is holding for half of it, and every other distance is
+// shorter, so working out distances is safe as long as that fits.
+pub fn check_time(line: &str, token: &str, time: usize) -> Result<(), ParseError> {
+    match (time / 2).checked_mul(time - time / 2) {
+        Some(_) => Ok(()),
+        None => Err(parse::invalid(line, token, format!("a race of {} is too long to work out its distances", time))),
+    }
+}
+
+// Returns what follows `label` on the line at `index`, e.g. the numbers in "Time:      7  15   30".
+pub fn labelled_line<'a>(slice: &[&'a str], index: usize, label: &str) -> Result<&'a str, ParseError> {
+    match slice.get(index) {
+        Some(line) => parse::expect_prefix(line, line, label).map_err(|e| e.on_line(index + 1)),
+        None => {
+            let last_line = slice.last().copied().unwrap_or("");
+            Err(parse::missing_after(last_line, last_line, &format!("a line starting with \"{}\"", label))
+                .on_line(slice.len().max(1)))
+        },
+    }
 }
 
-pub fn parse_lines(slice: &[&str]) -> Vec<Race> {
-    let times = parse_numbers_string(&slice[0][5..(slice[0].len())]);
-    let distances = parse_numbers_string(&slice[1][9..(slice[1].len())]);
-    
+pub fn parse_lines(slice: &[&str]) -> Result<Vec<Race>, ParseError> {
+    let times_str = labelled_line(slice, 0, "Time:")?;
+    let distances_str = labelled_line(slice, 1, "Distance:")?;
+    let times = parse_numbers_string(slice[0], times_str).map_err(|e| e.on_line(1))?;
+    for (token, time) in times_str.split_whitespace().zip(&times) {
+        check_time(slice[0], token, *time).map_err(|e| e.on_line(1))?;
+    }
+    let distances = parse_numbers_string(slice[1], distances_str).map_err(|e| e.on_line(2))?;
+    if times.len() != distances.len() {
+        return Err(parse::invalid(slice[1], distances_str, format!(
+            "found {} times but {} distances",
+            times.len(),
+            distances.len(),
+        )).on_line(2))
+    }
+
     let to_return: Vec<Race> = std::iter::zip(times, distances)
         .map(|tuple| Race { time_allowed: tuple.0, record_distance: tuple.1 })
         .collect();
 
-    to_return
+    Ok(to_return)
 }
 
 #[cfg(test)]
@@ -47,9 +76,26 @@ mod tests {
 "Time:      7  15   30
 Distance:  9  40  200"
         );
-        let races = parse_lines(&(input.lines().collect::<Vec<&str>>())[..]);
+        let races = parse_lines(&(input.lines().collect::<Vec<&str>>())[..]).unwrap();
         let product: usize = races.iter().map(|race| race.number_of_ways_to_win()).product();
         let expected_product: usize = 288;
         assert_eq!(expected_product, product);
     }
+    #[test]
+    fn parse_errors() {
+        let error = parse_lines(&["Time: 7 15", "Distance: 9"]).unwrap_err();
+        assert_eq!((2, 10), (error.line, error.column));
+
+        let error = parse_lines(&["Time: 7 15", "Distanse: 9 40"]).unwrap_err();
+        assert_eq!((2, 1, 9), (error.line, error.column, error.len));
+
+        let error = parse_lines(&["Time: 7 1S", "Distance: 9 40"]).unwrap_err();
+        assert_eq!((1, 9), (error.line, error.column));
+
+        let error = parse_lines(&["Time: 7 15"]).unwrap_err();
+        assert_eq!((1, 11), (error.line, error.column));
+
+        let error = parse_lines(&["Time: 7 99999999999999999", "Distance: 9 40"]).unwrap_err();
+        assert_eq!((1, 9, 17), (error.line, error.column, error.len));
+    }
 }
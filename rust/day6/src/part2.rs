@@ -1,20 +1,28 @@
-use crate::part1::Race;
+use aoc_common::{ parse, ParseError, ParseErrorKind };
 
-pub fn parse_numbers_string(s: &str) -> usize {
-    s
-        .trim()
-        .split(' ')
-        .filter(|s| s != &"")
-        .collect::<String>()
-        .parse()
-        .unwrap()
+use crate::part1::{ check_time, labelled_line, Race };
+
+// The spaces between the numbers are ignored, so "7  15   30" is 71530.
+pub fn parse_numbers_string(line: &str, s: &str) -> Result<usize, ParseError> {
+    crate::part1::parse_numbers_string(line, s)?;
+    let digits: String = s.split_whitespace().collect();
+    if digits.is_empty() {
+        return Err(parse::missing_after(line, s, "a number"))
+    }
+    digits.parse().map_err(|e: std::num::ParseIntError| ParseError::at(line, s.trim(), ParseErrorKind::InvalidNumber {
+        text: digits.clone(),
+        reason: e.to_string(),
+    }))
 }
 
-pub fn parse_lines(slice: &[&str]) -> Race {
-    let time = parse_numbers_string(&slice[0][5..(slice[0].len())]);
-    let distance = parse_numbers_string(&slice[1][9..(slice[1].len())]);
-    
-    Race { time_allowed: time, record_distance: distance }
+pub fn parse_lines(slice: &[&str]) -> Result<Race, ParseError> {
+    let time_str = labelled_line(slice, 0, "Time:")?;
+    let distance_str = labelled_line(slice, 1, "Distance:")?;
+    let time = parse_numbers_string(slice[0], time_str).map_err(|e| e.on_line(1))?;
+    check_time(slice[0], time_str.trim(), time).map_err(|e| e.on_line(1))?;
+    let distance = parse_numbers_string(slice[1], distance_str).map_err(|e| e.on_line(2))?;
+
+    Ok(Race { time_allowed: time, record_distance: distance })
 }
 
 #[cfg(test)]
@@ -27,8 +35,15 @@ mod tests {
 "Time:      7  15   30
 Distance:  9  40  200"
         );
-        let race = parse_lines(&(input.lines().collect::<Vec<&str>>())[..]);
+        let race = parse_lines(&(input.lines().collect::<Vec<&str>>())[..]).unwrap();
         let expected_number_of_ways: usize = 71503;
         assert_eq!(expected_number_of_ways, race.number_of_ways_to_win());
     }
+    #[test]
+    fn too_big() {
+        let error = parse_lines(&["Time: 7 15", "Distance: 99999999999 99999999999"]).unwrap_err();
+        assert_eq!((2, 11, 23), (error.line, error.column, error.len));
+        let error = parse_lines(&["Time: 99999 99999", "Distance: 9 40"]).unwrap_err();
+        assert_eq!((1, 7, 11), (error.line, error.column, error.len));
+    }
 }
@@ -0,0 +1,36 @@
+use aoc_common::Rng;
+
+const CARDS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+
+// Picking from a few card values at a time makes pairs, full houses and so on much more likely.
+pub fn hand(rng: &mut Rng) -> String {
+    let mut values = CARDS;
+    rng.shuffle(&mut values[..]);
+    let values = &values[..rng.range(1..6)];
+    (0..5).map(|_| *rng.choose(values)).collect()
+}
+
+pub fn generate(rng: &mut Rng, hands: usize) -> Vec<String> {
+    (0..hands.max(1)).map(|_| format!("{} {}", hand(rng), rng.range(1..1001))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let lines = generate(&mut Rng::new(seed), 1 + seed as usize * 5);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert!(solve_part1(&lines_str[..]).unwrap() > 0);
+            assert!(solve_part2(&lines_str[..]).unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
@@ -0,0 +1,39 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::{ Bid, Category };
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    let mut hands: Vec<part1::CamelHand> = part1::parse_camel_hands(input)?;
+    part1::sort_camel_hands(&mut hands);
+    Ok(part1::calculate_total_winnings(&hands[..]))
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    let mut hands: Vec<part2::CamelHand> = part2::parse_camel_hands(input)?;
+    part2::sort_camel_hands(&mut hands);
+    Ok(part2::calculate_total_winnings(&hands[..]))
+}
+
+pub struct Day7;
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        part1::parse_camel_hands(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
@@ -1,7 +1,8 @@
 use std::str::FromStr;
 
-#[derive(Debug)]
-pub struct CardParseError;
+use aoc_common::{ parse, ParseError, VecMap };
+
+pub const CARD_NAMES: &str = "a card (2-9, T, J, Q, K or A)";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Card {
@@ -20,7 +21,7 @@ pub enum Card {
     Ace,
 }
 impl FromStr for Card {
-    type Err = CardParseError;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "2" => Ok(Card::Two),
@@ -36,7 +37,7 @@ impl FromStr for Card {
             "Q" => Ok(Card::Queen),
             "K" => Ok(Card::King),
             "A" => Ok(Card::Ace),
-            _ => Err(CardParseError),
+            _ => Err(parse::expected(s, s, CARD_NAMES)),
         }
     }
 }
@@ -52,80 +53,6 @@ pub enum Category {
     FiveOfAKind,
 }
 
-pub fn all_equal<A>(slice: &[A]) -> bool
-where
-    A: PartialEq
-{
-    if slice.is_empty() {
-        return false
-    }
-    slice.iter().all(|a| a == &slice[0])
-}
-
-pub struct VecMap<K, V> {
-    pub vec: Vec<(K, V)>
-}
-impl<K, V> VecMap<K, V> {
-    pub fn new() -> VecMap<K, V>
-    where
-        K: PartialEq,
-    {
-        VecMap { vec: Vec::new() }
-    }
-    pub fn from_tuples_slice(tuples: &[(K, V)]) -> VecMap<K, V>
-    where
-        K: Clone,
-        K: PartialEq,
-        V: Clone,
-    {
-        VecMap { vec: Vec::from(tuples) }
-    }
-    pub fn get_value(&self, key: &K) -> Option<&V>
-    where
-        K: PartialEq,
-    {
-        match self.get_id_from_key(key) {
-            Some(i) => Some(&self.vec[i].1),
-            None => None,
-        }
-    }
-    fn get_id_from_key(&self, key: &K) -> Option<usize>
-    where
-        K: PartialEq,
-    {
-        for i in 0..(self.vec.len()) {
-            if &self.vec[i].0 == key {
-                return Some(i)
-            }
-        }
-        None
-    }
-    pub fn update(&mut self, tuple: (K, V))
-    where
-        K: PartialEq
-    {
-        match self.get_id_from_key(&tuple.0) {
-            Some(i) => self.vec[i].1 = tuple.1,
-            None => self.vec.push(tuple),
-        }
-    }
-    pub fn update_with_fn(&mut self, key: K, identity: V, func: impl FnOnce(&V) -> V)
-    where
-        K: PartialEq
-    {
-        match self.get_id_from_key(&key) {
-            Some(i) => self.vec[i].1 = func(&self.vec[i].1),
-            None => self.update((key, identity)),
-        }
-    }
-    pub fn keys(&self) -> Vec<&K> {
-        self.vec.iter().map(|tuple| &tuple.0).collect()
-    }
-    pub fn values(&self) -> Vec<&V> {
-        self.vec.iter().map(|tuple| &tuple.1).collect()
-    }
-}
-
 pub fn get_category(hand: &[Card; 5]) -> Category {
     let mut vec_map: VecMap<Card, usize> = VecMap::new();
     for card in hand {
@@ -159,17 +86,27 @@ pub struct Hand {
     category: Category
 }
 impl Hand {
-    fn from_str(s: &str) -> Hand {
-        assert!(s.len() == 5);
-        let cards = s.chars()
-            .map(|c| Card::from_str(&c.to_string()).unwrap())
-            .collect::<Vec<Card>>()
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+    pub fn category(&self) -> Category {
+        self.category
+    }
+}
+impl FromStr for Hand {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards: Vec<Card> = s.char_indices()
+            .enumerate()
+            .map(|(n, (i, c))| Card::from_str(&s[i..(i + c.len_utf8())]).map_err(|e| e.shifted(n)))
+            .collect::<Result<_, _>>()?;
+        let cards: [Card; 5] = cards
             .try_into()
-            .unwrap();
-        Hand {
+            .map_err(|cards: Vec<Card>| parse::invalid(s, s, format!("a hand has 5 cards, found {}", cards.len())))?;
+        Ok(Hand {
             cards,
             category: get_category(&cards),
-        }
+        })
     }
 }
 impl PartialOrd for Hand {
@@ -205,11 +142,15 @@ pub fn calculate_total_winnings(hands: &[CamelHand]) -> usize {
     (0..(hands.len())).map(|i| hands[i].1 * (i + 1)).sum()
 }
 
-pub fn string_to_camel_hand(s: &str) -> CamelHand {
-    let mut s_iter = s.split(' ');
-    let hand = Hand::from_str(s_iter.next().unwrap());
-    let bid: usize = s_iter.next().unwrap().parse().unwrap();
-    (hand, bid)
+pub fn string_to_camel_hand(s: &str) -> Result<CamelHand, ParseError> {
+    let (hand_str, bid_str) = parse::split_once(s, s, ' ')?;
+    let hand = Hand::from_str(hand_str)?;
+    let bid: usize = parse::parse_number(s, bid_str.trim())?;
+    Ok((hand, bid))
+}
+
+pub fn parse_camel_hands(lines: &[&str]) -> Result<Vec<CamelHand>, ParseError> {
+    parse::parse_lines(lines, string_to_camel_hand)
 }
 
 #[cfg(test)]
@@ -218,11 +159,11 @@ mod tests {
 
     #[test]
     fn hand_ordering() {
-        let lowest = Hand::from_str("12344");
-        let middle = Hand::from_str("1KQQQ");
-        let high = Hand::from_str("21QQQ");
-        let highest = Hand::from_str("555AA");
-        let control_cards = vec![lowest.clone(), middle.clone(), high.clone(), highest.clone()];
+        let lowest = Hand::from_str("23544").unwrap();
+        let middle = Hand::from_str("2KQQQ").unwrap();
+        let high = Hand::from_str("32QQQ").unwrap();
+        let highest = Hand::from_str("555AA").unwrap();
+        let control_cards = vec![lowest, middle, high, highest];
         let mut sorted_cards = vec![middle, high, highest, lowest];
         sorted_cards.sort();
         assert_eq!(control_cards, sorted_cards)
@@ -237,10 +178,25 @@ KK677 28
 KTJJT 220
 QQQJA 483"
         );
-        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s)).collect();
+        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         sort_camel_hands(&mut hands);
         let total_winnings = calculate_total_winnings(&hands[..]);
         let expected_total_winnings: usize = 6440;
         assert_eq!(expected_total_winnings, total_winnings);
     }
+    #[test]
+    fn parse_errors() {
+        let lines = ["32T3K 765", "T55X5 684"];
+        let error = parse_camel_hands(&lines).unwrap_err();
+        assert_eq!((2, 4, 1), (error.line, error.column, error.len));
+
+        let error = string_to_camel_hand("KK67 28").unwrap_err();
+        assert_eq!((1, 4), (error.column, error.len));
+
+        let error = string_to_camel_hand("KK677 2B").unwrap_err();
+        assert_eq!((7, 2), (error.column, error.len));
+
+        let error = string_to_camel_hand("KK677").unwrap_err();
+        assert_eq!(6, error.column);
+    }
 }
@@ -1,8 +1,8 @@
-use std::str::FromStr;
-use crate::part1::{ Category, VecMap };
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug)]
-pub struct CardParseError;
+use aoc_common::{ parse, ParseError, VecMap };
+
+use crate::part1::{ Category, CARD_NAMES };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Card {
@@ -21,7 +21,7 @@ pub enum Card {
     Ace,
 }
 impl FromStr for Card {
-    type Err = CardParseError;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "J" => Ok(Card::Joker),
@@ -37,13 +37,13 @@ impl FromStr for Card {
             "Q" => Ok(Card::Queen),
             "K" => Ok(Card::King),
             "A" => Ok(Card::Ace),
-            _ => Err(CardParseError),
+            _ => Err(parse::expected(s, s, CARD_NAMES)),
         }
     }
 }
-impl ToString for Card {
-    fn to_string(&self) -> String {
-        String::from(match self {
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
             Card::Joker => "J",
             Card::Two => "2",
             Card::Three => "3",
@@ -138,17 +138,27 @@ pub struct Hand {
     category: Category
 }
 impl Hand {
-    fn from_str(s: &str) -> Hand {
-        assert!(s.len() == 5);
-        let cards = s.chars()
-            .map(|c| Card::from_str(&c.to_string()).unwrap())
-            .collect::<Vec<Card>>()
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+    pub fn category(&self) -> Category {
+        self.category
+    }
+}
+impl FromStr for Hand {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards: Vec<Card> = s.char_indices()
+            .enumerate()
+            .map(|(n, (i, c))| Card::from_str(&s[i..(i + c.len_utf8())]).map_err(|e| e.shifted(n)))
+            .collect::<Result<_, _>>()?;
+        let cards: [Card; 5] = cards
             .try_into()
-            .unwrap();
-        Hand {
+            .map_err(|cards: Vec<Card>| parse::invalid(s, s, format!("a hand has 5 cards, found {}", cards.len())))?;
+        Ok(Hand {
             cards,
             category: get_category(&cards),
-        }
+        })
     }
 }
 impl PartialOrd for Hand {
@@ -197,11 +207,15 @@ pub fn calculate_total_winnings(hands: &[CamelHand]) -> usize {
     (0..(hands.len())).map(|i| hands[i].1 * (i + 1)).sum()
 }
 
-pub fn string_to_camel_hand(s: &str) -> CamelHand {
-    let mut s_iter = s.split(' ');
-    let hand = Hand::from_str(s_iter.next().unwrap());
-    let bid: usize = s_iter.next().unwrap().parse().unwrap();
-    (hand, bid)
+pub fn string_to_camel_hand(s: &str) -> Result<CamelHand, ParseError> {
+    let (hand_str, bid_str) = parse::split_once(s, s, ' ')?;
+    let hand = Hand::from_str(hand_str)?;
+    let bid: usize = parse::parse_number(s, bid_str.trim())?;
+    Ok((hand, bid))
+}
+
+pub fn parse_camel_hands(lines: &[&str]) -> Result<Vec<CamelHand>, ParseError> {
+    parse::parse_lines(lines, string_to_camel_hand)
 }
 
 #[cfg(test)]
@@ -210,11 +224,11 @@ mod tests {
 
     #[test]
     fn hand_ordering() {
-        let lowest = Hand::from_str("2534J");
-        let middle = Hand::from_str("2KQQJ");
-        let high = Hand::from_str("32QQJ");
-        let highest = Hand::from_str("55JAA");
-        let control_cards = vec![lowest.clone(), middle.clone(), high.clone(), highest.clone()];
+        let lowest = Hand::from_str("2534J").unwrap();
+        let middle = Hand::from_str("2KQQJ").unwrap();
+        let high = Hand::from_str("32QQJ").unwrap();
+        let highest = Hand::from_str("55JAA").unwrap();
+        let control_cards = vec![lowest, middle, high, highest];
         let mut sorted_cards = vec![middle, high, highest, lowest];
         sorted_cards.sort();
         assert_eq!(control_cards, sorted_cards)
@@ -229,7 +243,7 @@ KK677 28
 KTJJT 220
 QQQJA 483"
         );
-        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s)).collect();
+        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         sort_camel_hands(&mut hands);
         let total_winnings = calculate_total_winnings(&hands[..]);
         let expected_total_winnings: usize = 5905;
@@ -238,19 +252,19 @@ QQQJA 483"
 
     #[test]
     fn category_116() {
-        let hand: CamelHand = (Hand::from_str("Q9TQJ"), 116);
+        let hand: CamelHand = (Hand::from_str("Q9TQJ").unwrap(), 116);
         let expected_category = Category::ThreeOfAKind;
         assert_eq!(expected_category, hand.0.category);
     }
     #[test]
     fn category_152() {
-        let hand: CamelHand = (Hand::from_str("Q4J94"), 152);
+        let hand: CamelHand = (Hand::from_str("Q4J94").unwrap(), 152);
         let expected_category = Category::ThreeOfAKind;
         assert_eq!(expected_category, hand.0.category);
     }
     #[test]
     fn category_193() {
-        let hand: CamelHand = (Hand::from_str("55T8T"), 193);
+        let hand: CamelHand = (Hand::from_str("55T8T").unwrap(), 193);
         let expected_category = Category::TwoPair;
         assert_eq!(expected_category, hand.0.category);
     }
@@ -279,7 +293,7 @@ T9522 668
 ATJTJ 879
 7TATT 11"
         );
-        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s)).collect();
+        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         sort_camel_hands(&mut hands);
         let total_winnings = calculate_total_winnings(&hands[..]);
         let sorted: Vec<CamelHand> = String::from(
@@ -303,7 +317,7 @@ Q9TQJ 116
 QQQQ2 419
 ATJTJ 879
 JJJJ8 619"
-        ).lines().map(|s| string_to_camel_hand(s)).collect();
+        ).lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         let expected_total_winnings: usize = 90816;
         assert_eq!(sorted.iter().map(|tuple| tuple.0.category).collect::<Vec<_>>(), hands.iter().map(|tuple| tuple.0.category).collect::<Vec<_>>());
         assert_eq!(expected_total_winnings, total_winnings);
@@ -353,7 +367,7 @@ J8488 252
 J4K72 114
 9Q4KK 303"
         );
-        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s)).collect();
+        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         sort_camel_hands(&mut hands);
         let total_winnings = calculate_total_winnings(&hands[..]);
         let sorted: Vec<CamelHand> = String::from(
@@ -397,7 +411,7 @@ JQJAQ 201
 QQQQ2 419
 ATJTJ 879
 JJJJ8 619"
-        ).lines().map(|s| string_to_camel_hand(s)).collect();
+        ).lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         let expected_totals: Vec<usize> = vec![851, (114 * 2), (477 * 3), (72 * 4), (597 * 5), (12 * 6), (146 * 7), (465 * 8), (109 * 9), (303 * 10), (668 * 11), (352 * 12), (193 * 13), (247 * 14), (453 * 15), (148 * 16), (811 * 17), (373 * 18), (529 * 19), (788 * 20), (136 * 21), (806 * 22), (576 * 23), (277 * 24), (11 * 25), (152 * 26), (116 * 27), (885 * 28), (704 * 29), (252 * 30), (201 * 31), (167 * 32), (417 * 33), (782 * 34), (63 * 35), (651 * 36), (855 * 37), (419 * 38), (879 * 39), (619 * 40)];
         let totals: Vec<usize> = (0..(hands.len())).map(|i| hands[i].1 * (i + 1)).collect();
         assert_eq!(expected_totals, totals);
@@ -425,8 +439,8 @@ KJ639 7
 7788J 1
 JJJJJ 2
 JJ2JJ 3"
-        ).lines().map(|s| string_to_camel_hand(s)).collect();
-        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s)).collect();
+        ).lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
+        let mut hands: Vec<CamelHand> = input.lines().map(|s| string_to_camel_hand(s).unwrap()).collect();
         sort_camel_hands(&mut hands);
         let total_winnings = calculate_total_winnings(&hands[..]);
         let expected_total_winnings: usize = 95;
@@ -438,7 +452,7 @@ JJ2JJ 3"
     #[test]
     fn another_edge_case() {
         let input = String::from("J68J4 42");
-        let (hand, _) = string_to_camel_hand(&input[..]);
+        let (hand, _) = string_to_camel_hand(&input[..]).unwrap();
         assert_eq!(Category::ThreeOfAKind, hand.category)
     }
 }
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use aoc_common::Rng;
+
+const MAX_GHOSTS: usize = 6;
+
+fn letter(rng: &mut Rng) -> char {
+    (b'A' + rng.range(0..26) as u8) as char
+}
+
+// Names are three letters like the puzzle's, or longer when there are too many nodes for that. Keeping
+// at least half the names free means picking one at random never takes long.
+fn name_length(names: usize) -> usize {
+    let mut length = 3;
+    // How many names of `length` letters end in B to Y.
+    let mut available: usize = 24 * 26 * 26;
+    while available < names.saturating_mul(2) {
+        length += 1;
+        available = available.saturating_mul(26);
+    }
+    length
+}
+
+// Names in the middle of a path can't end in A or Z, or part 2 would treat them as starts or ends.
+fn middle_name(rng: &mut Rng, used: &mut HashSet<String>, length: usize) -> String {
+    loop {
+        let last = (b'B' + rng.range(0..24) as u8) as char;
+        let name: String = (1..length).map(|_| letter(rng)).chain([last]).collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// The start and end of one ghost's path, e.g. "QRA" and "QRZ". The first ghost is AAA to ZZZ,
+// which is the path part 1 follows.
+fn ends(rng: &mut Rng, used: &mut HashSet<String>, ghost: usize) -> (String, String) {
+    if ghost == 0 {
+        used.insert(String::from("AAA"));
+        used.insert(String::from("ZZZ"));
+        return (String::from("AAA"), String::from("ZZZ"));
+    }
+    loop {
+        let prefix: String = [letter(rng), letter(rng)].iter().collect();
+        let (start, end) = (format!("{}A", prefix), format!("{}Z", prefix));
+        if !used.contains(&start) && !used.contains(&end) {
+            used.insert(start.clone());
+            used.insert(end.clone());
+            return (start, end);
+        }
+    }
+}
+
+fn node_line(name: &str, left: &str, right: &str) -> String {
+    format!("{} = ({}, {})", name, left, right)
+}
+
+// Every ghost walks through `steps` layers of one or two nodes. Both of a node's elements lead
+// into the next layer, so whatever the instructions say, every ghost reaches its Z node on
+// exactly the same step, which is what part 2's step by step search needs.
+pub fn generate(rng: &mut Rng, steps: usize) -> Vec<String> {
+    let steps = steps.max(1);
+    let instructions: String = (0..rng.range(1..21)).map(|_| if rng.chance(0.5) { 'L' } else { 'R' }).collect();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut nodes: Vec<String> = Vec::new();
+    let ghosts = rng.range(1..(MAX_GHOSTS + 1));
+    // Each layer between the start and end has at most two nodes.
+    let length = name_length(ghosts * (steps - 1) * 2);
+    for ghost in 0..ghosts {
+        let (start, end) = ends(rng, &mut used, ghost);
+        let mut layers: Vec<Vec<String>> = vec![vec![start]];
+        for _ in 1..steps {
+            let width = rng.range(1..3);
+            layers.push((0..width).map(|_| middle_name(rng, &mut used, length)).collect());
+        }
+        layers.push(vec![end]);
+        // Once at the end, go round again from the first layer after the start.
+        let after_end = layers[1].clone();
+        for (i, layer) in layers.iter().enumerate() {
+            let next = layers.get(i + 1).unwrap_or(&after_end);
+            for name in layer {
+                let (left, right) = match next.len() {
+                    1 => (&next[0], &next[0]),
+                    _ if rng.chance(0.5) => (&next[0], &next[1]),
+                    _ => (&next[1], &next[0]),
+                };
+                nodes.push(node_line(name, left, right));
+            }
+        }
+    }
+    rng.shuffle(&mut nodes[..]);
+
+    let mut to_return: Vec<String> = vec![instructions, String::new()];
+    to_return.extend(nodes);
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let steps = 1 + seed as usize * 3;
+            let lines = generate(&mut Rng::new(seed), steps);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            assert_eq!(steps, solve_part1(&lines_str[..]).unwrap());
+            assert_eq!(steps, solve_part2(&lines_str[..]).unwrap());
+        }
+    }
+
+    #[test]
+    fn more_nodes_than_three_letter_names() {
+        assert_eq!(3, name_length(100));
+        assert_eq!(4, name_length(16_224));
+        let steps = 20_000;
+        let lines = generate(&mut Rng::new(1), steps);
+        let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+        assert_eq!(steps, solve_part1(&lines_str[..]).unwrap());
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
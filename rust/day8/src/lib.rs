@@ -0,0 +1,36 @@
+use aoc_common::{ ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::{ parse_input, Element, Instruction, Network, Node };
+pub use part2::Traverser;
+
+pub fn solve_part1(input: &[&str]) -> Result<usize, ParseError> {
+    part1::calculate_how_many_steps(input)
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<usize, ParseError> {
+    part2::calculate_how_many_steps(input)
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        8
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        parse_input(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
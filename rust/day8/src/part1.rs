@@ -1,23 +1,23 @@
 use std::{fmt::Display, str::FromStr};
-use std::collections::HashMap;
+use std::collections::{ HashMap, HashSet };
 
-type Element = [String; 2];
-type Node = (String, Element);
+use aoc_common::{ parse, ParseError };
 
-#[derive(Debug)]
-struct InstructionParseError;
+pub type Element = [String; 2];
+pub type Node = (String, Element);
+pub type Network = HashMap<String, Element>;
 
-enum Instruction {
+pub enum Instruction {
     Left,
     Right,
 }
 impl FromStr for Instruction {
-    type Err = InstructionParseError;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.trim() {
             "L" => Ok(Instruction::Left),
             "R" => Ok(Instruction::Right),
-            _ => Err(InstructionParseError),
+            _ => Err(parse::expected(s, s, "an instruction (L or R)")),
         }
     }
 }
@@ -30,27 +30,34 @@ impl Display for Instruction {
     }
 }
 
-fn get_uppercase_string(s: &str) -> String {
-    s.chars().filter(|c| c.is_ascii_uppercase()).collect()
+fn parse_name<'a>(line: &str, name: &'a str) -> Result<&'a str, ParseError> {
+    if name.is_empty() {
+        return Err(parse::missing_after(line, name, "a node name"))
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(parse::expected(line, name, "a node name made of letters and digits"))
+    }
+    Ok(name)
+}
+
+// Like parse_node_line, but keeps the names as slices of the line so they can be pointed at later.
+fn parse_node_line_spans(s: &str) -> Result<(&str, [&str; 2]), ParseError> {
+    let (name, element) = parse::split_once(s, s, '=')?;
+    let name = parse_name(s, name.trim())?;
+    let element = element.trim();
+    let inner = match element.strip_prefix('(') {
+        Some(inner) => inner,
+        None if element.is_empty() => return Err(parse::missing_after(s, element, "'('")),
+        None => return Err(parse::expected(s, &element[..1], "'('")),
+    };
+    let inner = inner.strip_suffix(')').ok_or_else(|| parse::missing_after(s, inner, "')'"))?;
+    let (left, right) = parse::split_once(s, inner, ',')?;
+    Ok((name, [parse_name(s, left.trim())?, parse_name(s, right.trim())?]))
 }
 
-fn parse_node_line(s: &str) -> Node {
-    let mut s_equals_split = s.split('=');
-    let name: String = s_equals_split
-        .next()
-        .unwrap()
-        .trim()
-        .to_string();
-    let element: Element = s_equals_split
-        .next()
-        .unwrap()
-        .trim()
-        .split(',')
-        .map(|s| get_uppercase_string(s))
-        .collect::<Vec<String>>()
-        .try_into()
-        .unwrap();
-    (name, element)
+pub fn parse_node_line(s: &str) -> Result<Node, ParseError> {
+    let (name, element) = parse_node_line_spans(s)?;
+    Ok((String::from(name), element.map(String::from)))
 }
 
 fn follow_instruction(instruction: &Instruction, element: &Element) -> String {
@@ -60,19 +67,69 @@ fn follow_instruction(instruction: &Instruction, element: &Element) -> String {
     }
 }
 
-pub fn calculate_how_many_steps(input: &[&str]) -> usize {
-    let mut hash_map: HashMap<String, Element>= HashMap::new();
-    let mut input_lines = input.iter();
-    let instructions: Vec<Instruction> = input_lines
-        .next()
-        .unwrap()
-        .chars()
-        .map(|c| Instruction::from_str(&c.to_string()).unwrap())
+pub fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    let s = s.trim_end();
+    if s.is_empty() {
+        return Err(parse::missing_after(s, s, "a line of L and R instructions"))
+    }
+    s.char_indices()
+        .enumerate()
+        .map(|(n, (i, c))| Instruction::from_str(&s[i..(i + c.len_utf8())]).map_err(|e| e.shifted(n)))
+        .collect()
+}
+
+// Returns the instructions and the nodes in the order they were given.
+// Every node that is pointed to must also be defined exactly once.
+pub fn parse_input(input: &[&str]) -> Result<(Vec<Instruction>, Vec<Node>), ParseError> {
+    let instructions = parse_instructions(input.first().copied().unwrap_or(""))?;
+    if let Some(line) = input.get(1).filter(|l| !l.trim().is_empty()) {
+        return Err(parse::expected(line, line, "an empty line").on_line(2))
+    }
+
+    let node_lines = input.get(2..).unwrap_or(&[]);
+    let spans: Vec<(&str, [&str; 2])> = node_lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| parse_node_line_spans(l).map_err(|e| e.on_line(i + 3)))
+        .collect::<Result<_, _>>()?;
+    let mut names: HashSet<&str> = HashSet::new();
+    for (i, (name, _)) in spans.iter().enumerate() {
+        if !names.insert(name) {
+            return Err(parse::invalid(node_lines[i], name, format!("node {} is defined more than once", name))
+                .on_line(i + 3))
+        }
+    }
+    for (i, (_, element)) in spans.iter().enumerate() {
+        if let Some(name) = element.iter().find(|name| !names.contains(*name)) {
+            return Err(parse::invalid(node_lines[i], name, format!("node {} is never defined", name))
+                .on_line(i + 3))
+        }
+    }
+
+    let nodes: Vec<Node> = spans
+        .into_iter()
+        .map(|(name, element)| (String::from(name), element.map(String::from)))
         .collect();
-    input_lines.next();
-    let nodes: Vec<Node> = input_lines.map(|s| parse_node_line(s)).collect();
-    for node in nodes {
-        hash_map.insert(node.0, node.1);
+    Ok((instructions, nodes))
+}
+
+// For when the network is fine on its own but is missing a node the puzzle needs.
+pub fn missing_node(input: &[&str], description: &str) -> ParseError {
+    let last_line = input.last().copied().unwrap_or("");
+    parse::missing_after(last_line, last_line, description).on_line(input.len().max(1))
+}
+
+pub fn nodes_to_network(nodes: &[Node]) -> Network {
+    nodes.iter().cloned().collect()
+}
+
+pub fn calculate_how_many_steps(input: &[&str]) -> Result<usize, ParseError> {
+    let (instructions, nodes) = parse_input(input)?;
+    let hash_map = nodes_to_network(&nodes[..]);
+    for name in ["AAA", "ZZZ"] {
+        if !hash_map.contains_key(name) {
+            return Err(missing_node(input, &format!("a node named {}", name)))
+        }
     }
     let mut current_node = String::from("AAA");
     let mut steps: usize = 0;
@@ -88,12 +145,11 @@ pub fn calculate_how_many_steps(input: &[&str]) -> usize {
             }
         }
     }
-    steps
+    Ok(steps)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
     use super::*;
 
     #[test]
@@ -109,7 +165,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)"
         );
-        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]);
+        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]).unwrap();
         let expected_steps: usize = 2;
         assert_eq!(expected_steps, steps);
     }
@@ -123,8 +179,29 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)"
         );
-        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]);
+        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]).unwrap();
         let expected_steps: usize = 6;
         assert_eq!(expected_steps, steps);
     }
-}
\ No newline at end of file
+    #[test]
+    fn parse_errors() {
+        let error = calculate_how_many_steps(&["RLX"]).unwrap_err();
+        assert_eq!((1, 3, 1), (error.line, error.column, error.len));
+
+        let error = calculate_how_many_steps(&["RL", "", "AAA = (BBB, ZZZ)", "ZZZ = (ZZZ ZZZ)"]).unwrap_err();
+        assert_eq!((4, 15), (error.line, error.column));
+
+        let error = calculate_how_many_steps(&["RL", "", "AAA = (BBB, ZZZ)", "ZZZ = (ZZZ, ZZZ)"]).unwrap_err();
+        assert_eq!((3, 8, 3), (error.line, error.column, error.len));
+        assert_eq!("node BBB is never defined", error.kind.to_string());
+
+        let error = calculate_how_many_steps(&["RL", "", "AAA = (AAA, ZZZ)", "AAA = (ZZZ, ZZZ)"]).unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+
+        let error = calculate_how_many_steps(&["RL", "", "AAA = (AAA, AAA)"]).unwrap_err();
+        assert_eq!((3, 17), (error.line, error.column));
+
+        let error = calculate_how_many_steps(&["RL", "", "AAA = [AAA, AAA]"]).unwrap_err();
+        assert_eq!((3, 7), (error.line, error.column));
+    }
+}
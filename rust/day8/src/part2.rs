@@ -1,64 +1,6 @@
-use std::{fmt::Display, str::FromStr};
-use std::collections::HashMap;
+use aoc_common::ParseError;
 
-type Element = [String; 2];
-type Node = (String, Element);
-
-#[derive(Debug)]
-pub struct InstructionParseError;
-
-pub enum Instruction {
-    Left,
-    Right,
-}
-impl FromStr for Instruction {
-    type Err = InstructionParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim() {
-            "L" => Ok(Instruction::Left),
-            "R" => Ok(Instruction::Right),
-            _ => Err(InstructionParseError),
-        }
-    }
-}
-impl Display for Instruction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", match self {
-            Instruction::Left => "L",
-            Instruction::Right => "R",
-        })
-    }
-}
-
-fn get_uppercase_string(s: &str) -> String {
-    s.chars().filter(|c| c.is_ascii_uppercase()).collect()
-}
-
-fn parse_node_line(s: &str) -> Node {
-    let mut s_equals_split = s.split('=');
-    let name: String = s_equals_split
-        .next()
-        .unwrap()
-        .trim()
-        .to_string();
-    let element: Element = s_equals_split
-        .next()
-        .unwrap()
-        .trim()
-        .split(',')
-        .map(|s| get_uppercase_string(s))
-        .collect::<Vec<String>>()
-        .try_into()
-        .unwrap();
-    (name, element)
-}
-
-fn follow_instruction(instruction: &Instruction, element: &Element) -> String {
-    match instruction {
-        Instruction::Left => element[0].clone(),
-        Instruction::Right => element[1].clone(),
-    }
-}
+use crate::part1::{ missing_node, nodes_to_network, parse_input, Instruction, Network };
 
 pub struct Traverser {
     pub current_node: String,
@@ -68,7 +10,7 @@ impl Traverser {
     pub fn new(starting_node: &str) -> Traverser {
         Traverser { current_node: String::from(starting_node), finished: false }
     }
-    pub fn make_move(&mut self, instruction: &Instruction, hash_map: &HashMap<String, Element>) {
+    pub fn make_move(&mut self, instruction: &Instruction, hash_map: &Network) {
         self.current_node = match instruction {
             Instruction::Left => hash_map.get(&self.current_node).unwrap()[0].clone(),
             Instruction::Right => hash_map.get(&self.current_node).unwrap()[1].clone(),
@@ -84,7 +26,7 @@ pub fn traversers_finished(traversers: &[Traverser]) -> bool {
 pub fn traversers_move(
     traversers: &mut [Traverser], 
     instruction: &Instruction,
-    hash_map: &HashMap<String, Element>
+    hash_map: &Network
 )
 {
     for t in traversers {
@@ -92,19 +34,11 @@ pub fn traversers_move(
     }
 }
 
-pub fn calculate_how_many_steps(input: &[&str]) -> usize {
-    let mut hash_map: HashMap<String, Element>= HashMap::new();
-    let mut input_lines = input.iter();
-    let instructions: Vec<Instruction> = input_lines
-        .next()
-        .unwrap()
-        .chars()
-        .map(|c| Instruction::from_str(&c.to_string()).unwrap())
-        .collect();
-    input_lines.next();
-    let nodes: Vec<Node> = input_lines.map(|s| parse_node_line(s)).collect();
-    for node in nodes.clone() {
-        hash_map.insert(node.0, node.1);
+pub fn calculate_how_many_steps(input: &[&str]) -> Result<usize, ParseError> {
+    let (instructions, nodes) = parse_input(input)?;
+    let hash_map = nodes_to_network(&nodes[..]);
+    if !hash_map.keys().any(|name| name.ends_with('Z')) {
+        return Err(missing_node(input, "a node ending in Z"))
     }
 
     let starting_nodes: Vec<String> = nodes
@@ -112,6 +46,9 @@ pub fn calculate_how_many_steps(input: &[&str]) -> usize {
         .map(|node| node.0.clone())
         .filter(|name| name.ends_with("A"))
         .collect();
+    if starting_nodes.is_empty() {
+        return Err(missing_node(input, "a node ending in A"))
+    }
 
     let mut traversers: Vec<Traverser> = Vec::new();
     for node in starting_nodes {
@@ -130,7 +67,7 @@ pub fn calculate_how_many_steps(input: &[&str]) -> usize {
             }
         }
     }
-    steps
+    Ok(steps)
 }
 
 #[cfg(test)]
@@ -151,7 +88,7 @@ QQC = (QQZ, QQZ)
 QQZ = (QQB, QQB)
 XXX = (XXX, XXX)"
         );
-        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]);
+        let steps = calculate_how_many_steps(&input.lines().collect::<Vec<&str>>()[..]).unwrap();
         let expected_steps: usize = 6;
         assert_eq!(expected_steps, steps);
     }
@@ -0,0 +1,52 @@
+use aoc_common::Rng;
+
+const LENGTH: usize = 21;
+const MAX_DEGREE: usize = 5;
+
+// The values of a random polynomial at 0, 1, 2..., which is what every line in the puzzle is.
+pub fn sequence(rng: &mut Rng) -> Vec<isize> {
+    let coefficients: Vec<isize> = (0..=rng.range(0..(MAX_DEGREE + 1)))
+        .map(|_| rng.range(0..21) as isize - 10)
+        .collect();
+    (0..LENGTH as isize)
+        .map(|x| coefficients.iter().rev().fold(0, |total, c| total * x + c))
+        .collect()
+}
+
+pub fn generate(rng: &mut Rng, lines: usize) -> Vec<String> {
+    (0..lines.max(1))
+        .map(|_| sequence(rng).iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ next_in_sequence, previous_in_sequence, solve_part1, solve_part2 };
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..50 {
+            let lines = generate(&mut Rng::new(seed), 1 + seed as usize * 3);
+            let lines_str: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+            solve_part1(&lines_str[..]).unwrap();
+            solve_part2(&lines_str[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn extrapolates_the_polynomial() {
+        let mut rng = Rng::new(11);
+        for _ in 0..20 {
+            let whole = sequence(&mut rng);
+            let middle = &whole[1..(LENGTH - 1)];
+            assert_eq!(whole[LENGTH - 1], next_in_sequence(middle));
+            assert_eq!(whole[0], previous_in_sequence(middle));
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(&mut Rng::new(7), 20), generate(&mut Rng::new(7), 20));
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::{ parse, ParseError, ParseResult, Rng, Solution, SolveResult };
+
+pub mod generate;
+pub mod part1;
+pub mod part2;
+
+pub use part1::{ difference_sequence, extrapolate, next_in_sequence, parse_sequences, str_to_sequence };
+pub use part2::previous_in_sequence;
+
+// Adds up `extrapolated` of every sequence, pointing at the line that takes the sum too far if it overflows.
+fn sum_extrapolated(input: &[&str], extrapolated: fn(&[isize]) -> isize) -> Result<isize, ParseError> {
+    let mut sum: isize = 0;
+    for (i, nums) in parse_sequences(input)?.iter().enumerate() {
+        sum = sum.checked_add(extrapolated(&nums[..])).ok_or_else(|| {
+            parse::invalid(input[i], input[i], String::from("the sum of the extrapolated numbers is too big")).on_line(i + 1)
+        })?;
+    }
+    Ok(sum)
+}
+
+pub fn solve_part1(input: &[&str]) -> Result<isize, ParseError> {
+    sum_extrapolated(input, next_in_sequence)
+}
+
+pub fn solve_part2(input: &[&str]) -> Result<isize, ParseError> {
+    sum_extrapolated(input, previous_in_sequence)
+}
+
+pub struct Day9;
+impl Solution for Day9 {
+    fn day(&self) -> u8 {
+        9
+    }
+    fn parse(&self, input: &[&str]) -> ParseResult {
+        parse_sequences(input)?;
+        Ok(())
+    }
+    fn part1(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part1(input)?.to_string())
+    }
+    fn part2(&self, input: &[&str]) -> SolveResult {
+        Ok(solve_part2(input)?.to_string())
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Vec<String> {
+        generate::generate(rng, size)
+    }
+}
@@ -1,3 +1,4 @@
+use aoc_common::{ all_equal, parse, ParseError };
 
 pub fn difference_sequence(seq: &[isize]) -> Vec<isize> {
     if seq.len() < 2 {
@@ -10,36 +11,43 @@ pub fn difference_sequence(seq: &[isize]) -> Vec<isize> {
     to_return
 }
 
-pub fn all_equal<A>(slice: &[A]) -> bool
-where
-    A: PartialEq
-{
-    if slice.is_empty() {
-        return false
+// The numbers before and after the sequence, or None if working them out overflows.
+pub fn extrapolate(seq: &[isize]) -> Option<(isize, isize)> {
+    let mut sequences: Vec<Vec<isize>> = vec![seq.to_vec()];
+    while !all_equal(sequences.last().unwrap()) {
+        let differences = sequences.last().unwrap().windows(2).map(|w| w[1].checked_sub(w[0])).collect::<Option<_>>()?;
+        sequences.push(differences);
+    }
+    let constant_number = sequences.last().unwrap()[0];
+    let (mut previous, mut next) = (constant_number, constant_number);
+    for sequence in sequences.iter().rev().skip(1) {
+        previous = sequence.first().unwrap().checked_sub(previous)?;
+        next = sequence.last().unwrap().checked_add(next)?;
     }
-    slice.iter().all(|a| a == &slice[0])
+    Some((previous, next))
 }
 
-
+// Panics if the sequence wasn't checked by `str_to_sequence` and overflows.
 pub fn next_in_sequence(seq: &[isize]) -> isize {
-    let mut sequences: Vec<Vec<isize>> = vec![seq.to_vec()];
-    while !all_equal(&sequences.last().unwrap()) {
-        sequences.push(difference_sequence(&sequences.last().unwrap()));
+    extrapolate(seq).expect("the sequence should extrapolate without overflowing").1
+}
+
+pub fn str_to_sequence(s: &str) -> Result<Vec<isize>, ParseError> {
+    let sequence: Vec<isize> = parse::numbers(s, s)?;
+    if sequence.is_empty() {
+        return Err(parse::missing_after(s, s, "a sequence of numbers"))
     }
-    let constant_number = sequences.last().unwrap()[0];
-    (*sequences.last_mut().unwrap()).push(constant_number);
-    for i in (1..(sequences.len())).rev() {
-        let current_next_number = sequences[i].last().unwrap() + sequences[i - 1].last().unwrap();
-        sequences[i - 1].push(current_next_number);
+    if extrapolate(&sequence[..]).is_none() {
+        // Point at the number whose difference from the one before overflows, if it's that.
+        let first_overflow = sequence.windows(2).position(|w| w[1].checked_sub(w[0]).is_none());
+        let part = first_overflow.and_then(|i| s.split_whitespace().nth(i + 1)).unwrap_or(s);
+        return Err(parse::invalid(s, part, String::from("the differences in this sequence are too big to work out")))
     }
-    *sequences.first().unwrap().last().unwrap()
+    Ok(sequence)
 }
 
-pub fn str_to_sequence(s: &str) -> Vec<isize> {
-    s
-        .split(' ')
-        .map(|str_num| str_num.parse::<isize>().unwrap())
-        .collect()
+pub fn parse_sequences(lines: &[&str]) -> Result<Vec<Vec<isize>>, ParseError> {
+    parse::parse_lines(lines, str_to_sequence)
 }
 
 #[cfg(test)]
@@ -62,4 +70,22 @@ mod tests {
         let expected_sum: isize = 114;
         assert_eq!(expected_sum, sum);
     }
-}
\ No newline at end of file
+    #[test]
+    fn parse_errors() {
+        let error = parse_sequences(&["0 3 6", "1 3 - 6"]).unwrap_err();
+        assert_eq!((2, 5, 1), (error.line, error.column, error.len));
+
+        let error = parse_sequences(&["0 3 6", ""]).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+
+        let error = parse_sequences(&["9223372036854775807 -9223372036854775808"]).unwrap_err();
+        assert_eq!((1, 21, 20), (error.line, error.column, error.len));
+        // Fine at first, but the next number is too big.
+        let error = parse_sequences(&["0 9223372036854775806"]).unwrap_err();
+        assert_eq!((1, 1, 21), (error.line, error.column, error.len));
+
+        // Each line is fine, but not all of them together.
+        let big = "4611686018427387903 4611686018427387903";
+        assert_eq!(3, crate::solve_part1(&[big, big, big]).unwrap_err().line);
+    }
+}
@@ -1,59 +1,8 @@
+use crate::part1::extrapolate;
 
-pub fn difference_sequence(seq: &[isize]) -> Vec<isize> {
-    if seq.len() < 2 {
-        return seq.to_vec();
-    }
-    let mut to_return: Vec<isize> = Vec::new();
-    for i in 1..(seq.len()) {
-        to_return.push(seq[i] - seq[i - 1]);
-    }
-    to_return
-}
-
-pub fn all_equal<A>(slice: &[A]) -> bool
-where
-    A: PartialEq
-{
-    if slice.is_empty() {
-        return false
-    }
-    slice.iter().all(|a| a == &slice[0])
-}
-
-
-pub fn next_in_sequence(seq: &[isize]) -> isize {
-    let mut sequences: Vec<Vec<isize>> = vec![seq.to_vec()];
-    while !all_equal(&sequences.last().unwrap()) {
-        sequences.push(difference_sequence(&sequences.last().unwrap()));
-    }
-    let constant_number = sequences.last().unwrap()[0];
-    (*sequences.last_mut().unwrap()).push(constant_number);
-    for i in (1..(sequences.len())).rev() {
-        let current_next_number = sequences[i].last().unwrap() + sequences[i - 1].last().unwrap();
-        sequences[i - 1].push(current_next_number);
-    }
-    *sequences.first().unwrap().last().unwrap()
-}
-
+// Panics if the sequence wasn't checked by `str_to_sequence` and overflows.
 pub fn previous_in_sequence(seq: &[isize]) -> isize {
-    let mut sequences: Vec<Vec<isize>> = vec![seq.to_vec()];
-    while !all_equal(&sequences.last().unwrap()) {
-        sequences.push(difference_sequence(&sequences.last().unwrap()));
-    }
-    let constant_number = sequences.last().unwrap()[0];
-    (*sequences.last_mut().unwrap()).insert(0, constant_number);
-    for i in (1..(sequences.len())).rev() {
-        let current_previous = sequences[i - 1].first().unwrap() - sequences[i].first().unwrap();
-        sequences[i - 1].insert(0, current_previous);
-    }
-    *sequences.first().unwrap().first().unwrap()
-}
-
-pub fn str_to_sequence(s: &str) -> Vec<isize> {
-    s
-        .split(' ')
-        .map(|str_num| str_num.parse::<isize>().unwrap())
-        .collect()
+    extrapolate(seq).expect("the sequence should extrapolate without overflowing").0
 }
 
 #[cfg(test)]